@@ -1,21 +1,55 @@
-use std::env;
+use std::{env, path::PathBuf};
+
+const DEFAULT_PORT: u16 = 18412;
 
 pub struct Config {
     pub enable_variable_font: bool,
+    pub font_dirs: Vec<PathBuf>,
+    pub host: String,
+    pub port: u16,
+    pub require_unicode: bool,
+    pub scan_threads: usize,
 }
 
 impl Config {
-    pub fn new() -> Config {
-        Config {
+    /// Reads the configuration from the environment; command line flags are
+    /// applied on top of it by the binary.
+    pub fn new() -> Result<Config, String> {
+        Ok(Config {
             enable_variable_font: env::var("FIGMA_AGENT_VARIABLE_FONT")
                 .map(|value| value != "false" && value != "0")
                 .unwrap_or(true),
-        }
+            font_dirs: env::var_os("FIGMA_AGENT_FONT_DIRS")
+                .map(|value| env::split_paths(&value).collect())
+                .unwrap_or_default(),
+            host: env::var("FIGMA_AGENT_HOST").unwrap_or_else(|_| "localhost".to_owned()),
+            port: match env::var("FIGMA_AGENT_PORT") {
+                Ok(value) => value
+                    .parse()
+                    .map_err(|_| format!("invalid FIGMA_AGENT_PORT: {}", value))?,
+                Err(_) => DEFAULT_PORT,
+            },
+            require_unicode: env::var("FIGMA_AGENT_REQUIRE_UNICODE")
+                .map(|value| value != "false" && value != "0")
+                .unwrap_or(false),
+            // 0 lets rayon use one thread per CPU.
+            scan_threads: env::var("FIGMA_AGENT_SCAN_THREADS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(0),
+        })
     }
 }
 
 impl Default for Config {
     fn default() -> Self {
-        Self::new()
+        Config {
+            enable_variable_font: true,
+            font_dirs: Vec::new(),
+            host: "localhost".to_owned(),
+            port: DEFAULT_PORT,
+            require_unicode: false,
+            scan_threads: 0,
+        }
     }
 }
@@ -1,7 +1,6 @@
-use freetype::{TT_MS_ID_SYMBOL_CS, TT_MS_ID_UNICODE_CS, TT_PLATFORM_MICROSOFT};
 use serde::{Deserialize, Serialize};
 
-use crate::FT;
+use crate::FT_POOL;
 
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -33,24 +32,17 @@ pub struct FontInstance {
 }
 
 impl Font {
-    pub fn new<P>(path: P, index: isize) -> Option<Font>
+    pub fn new<P>(path: P, index: isize) -> Result<Font, freetype::Error>
     where
         P: AsRef<str>,
     {
-        let face = FT.face_from_file(path, index)?;
+        let library = FT_POOL.get()?;
+        let face = library.face_from_file(path, index)?;
         let mm_var = face.mm_var()?;
 
-        let get_name = |name_id| {
-            let sfnt_name = face.find_sfnt_name(|item| {
-                item.name_id() == name_id
-                    && item.platform_id() == TT_PLATFORM_MICROSOFT as u16
-                    && (item.encoding_id() == TT_MS_ID_SYMBOL_CS as u16
-                        || item.encoding_id() == TT_MS_ID_UNICODE_CS as u16)
-            })?;
-            sfnt_name.name()
-        };
+        let get_name = |name_id| face.sfnt_name_for(name_id, None);
 
-        Some(Font {
+        Ok(Font {
             postscript_name: face.postscript_name().unwrap_or("").to_owned(),
             family_name: face.family_name().unwrap_or("").to_owned(),
             style_name: face.style_name().unwrap_or("").to_owned(),
@@ -58,7 +50,9 @@ impl Font {
             variation_axes: mm_var
                 .axes()
                 .map(|axis| FontVariationAxis {
-                    name: get_name(axis.name_id()).unwrap_or_else(|| "".to_owned()),
+                    name: get_name(axis.name_id())
+                        .or_else(|| axis.name().map(ToOwned::to_owned))
+                        .unwrap_or_else(|| "".to_owned()),
                     tag: axis.tag_string().unwrap_or_else(|| "".to_owned()),
                     min: axis.min(),
                     max: axis.max(),
@@ -70,10 +64,9 @@ impl Font {
             instances: mm_var
                 .named_styles()
                 .map(|named_style| FontInstance {
-                    name: get_name(named_style.name_id()).unwrap_or_else(|| "".to_owned()),
+                    name: named_style.name().unwrap_or_else(|| "".to_owned()),
                     postscript_name: named_style
-                        .postscript_name_id()
-                        .and_then(get_name)
+                        .postscript_name()
                         .unwrap_or_else(|| "".to_owned()),
                     coordinates: named_style.coordinates().collect(),
                 })
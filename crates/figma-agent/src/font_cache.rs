@@ -1,25 +1,40 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs::{self, File},
     path::{Path, PathBuf},
     time::SystemTime,
 };
 
+use itertools::Itertools;
+use log::{debug, warn};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::Font;
+use crate::{scan_install, Font, FT_POOL};
 
-const VERSION: i32 = 2;
+const VERSION: i32 = 5;
+// Entries only hold a few names, except for variable fonts, so this
+// comfortably covers large collections while keeping the cache file small.
+const CAPACITY: usize = 16384;
+
+type Version = (i32, i32, i32);
 
 pub struct FontCache {
     path: PathBuf,
     data: FontCacheData,
+    clock: u64,
+    // Keys by `last_used`, so that the least recently used one comes first.
+    recency: BTreeMap<u64, String>,
 }
 
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct FontCacheData {
     version: i32,
+    // Fonts are parsed differently across library versions, so an upgrade
+    // invalidates the whole cache. They are set when the cache is written.
+    freetype_version: Option<Version>,
+    fontconfig_version: Option<Version>,
     fonts: HashMap<String, FontCacheFontData>,
 }
 
@@ -27,6 +42,8 @@ impl Default for FontCacheData {
     fn default() -> Self {
         FontCacheData {
             version: VERSION,
+            freetype_version: None,
+            fontconfig_version: None,
             fonts: HashMap::new(),
         }
     }
@@ -35,16 +52,36 @@ impl Default for FontCacheData {
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct FontCacheFontData {
-    time: SystemTime,
-    font: Font,
+    modified_time: SystemTime,
+    size: u64,
+    // Only loaded for variable fonts.
+    font: Option<Font>,
+    has_unicode_cmap: Option<bool>,
+    #[serde(skip)]
+    last_used: u64,
 }
 
 impl FontCacheFontData {
-    fn new(font: Font) -> FontCacheFontData {
-        FontCacheFontData {
-            time: SystemTime::now(),
-            font,
-        }
+    fn new(path: &str) -> Option<FontCacheFontData> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(FontCacheFontData {
+            modified_time: metadata.modified().ok()?,
+            size: metadata.len(),
+            font: None,
+            has_unicode_cmap: None,
+            last_used: 0,
+        })
+    }
+
+    /// Whether the file still has the modification time and size it had
+    /// when it was loaded.
+    fn is_fresh(&self, path: &str) -> bool {
+        fs::metadata(path).is_ok_and(|metadata| {
+            metadata
+                .modified()
+                .is_ok_and(|modified_time| modified_time == self.modified_time)
+                && metadata.len() == self.size
+        })
     }
 }
 
@@ -56,6 +93,8 @@ impl FontCache {
         FontCache {
             path: path.as_ref().to_owned(),
             data: Default::default(),
+            clock: 0,
+            recency: BTreeMap::new(),
         }
     }
 
@@ -63,38 +102,182 @@ impl FontCache {
     where
         P: AsRef<str>,
     {
-        let key = format!("{}:{}", path.as_ref(), index);
-
-        if let Some(font_data) = self.data.fonts.get(&key) {
-            let modified_time =
-                fs::metadata(path.as_ref()).and_then(|metadata| metadata.modified());
-            if let Ok(modified_time) = modified_time {
-                if modified_time <= font_data.time {
-                    return Some(font_data.font.to_owned());
-                }
-            }
+        if let Some(font) = self.get_fresh_font(path.as_ref(), index) {
+            return Some(font.to_owned());
         }
 
-        let font = Font::new(path, index)?;
-        let font_data = FontCacheFontData::new(font.to_owned());
-        self.data.fonts.insert(key, font_data);
+        let font = match Font::new(path.as_ref(), index) {
+            Ok(font) => font,
+            Err(error) => {
+                warn!("failed to load {}:{}: {}", path.as_ref(), index, error);
+                return None;
+            }
+        };
+        if let Some(font_data) = self.entry(path.as_ref(), index) {
+            font_data.font = Some(font.to_owned());
+        }
         Some(font)
     }
 
+    /// Loads every missing or outdated font on `SCAN_POOL`, so that the
+    /// following `get` calls are served from the cache.
+    pub fn prefetch<'a, I>(&mut self, fonts: I)
+    where
+        I: IntoIterator<Item = (&'a str, isize)>,
+    {
+        let fonts: Vec<_> = fonts
+            .into_iter()
+            .unique()
+            .filter(|&(path, index)| self.get_fresh_font(path, index).is_none())
+            .collect();
+
+        // Failures are left to `get`, which logs them.
+        let loaded: Vec<_> = scan_install(|| {
+            fonts
+                .into_par_iter()
+                .filter_map(|(path, index)| Some((path, index, Font::new(path, index).ok()?)))
+                .collect()
+        });
+        for (path, index, font) in loaded {
+            if let Some(font_data) = self.entry(path, index) {
+                font_data.font = Some(font);
+            }
+        }
+    }
+
+    /// Whether the face has a Unicode charmap. Faces that cannot be opened
+    /// have none; this only fails if FreeType is not available.
+    pub fn has_unicode_cmap<P>(&mut self, path: P, index: isize) -> Result<bool, freetype::Error>
+    where
+        P: AsRef<str>,
+    {
+        if let Some(has_unicode_cmap) = self
+            .get_fresh(path.as_ref(), index)
+            .and_then(|font_data| font_data.has_unicode_cmap)
+        {
+            return Ok(has_unicode_cmap);
+        }
+
+        let has_unicode_cmap = load_unicode_cmap(path.as_ref(), index)?;
+        if let Some(font_data) = self.entry(path.as_ref(), index) {
+            font_data.has_unicode_cmap = Some(has_unicode_cmap);
+        }
+        Ok(has_unicode_cmap)
+    }
+
+    /// Checks the charmaps of every missing or outdated face on `SCAN_POOL`,
+    /// so that the following `has_unicode_cmap` calls are served from the
+    /// cache.
+    pub fn prefetch_unicode_cmaps<'a, I>(&mut self, fonts: I)
+    where
+        I: IntoIterator<Item = (&'a str, isize)>,
+    {
+        let fonts: Vec<_> = fonts
+            .into_iter()
+            .unique()
+            .filter(|&(path, index)| {
+                self.get_fresh(path, index)
+                    .and_then(|font_data| font_data.has_unicode_cmap)
+                    .is_none()
+            })
+            .collect();
+
+        // Failures are left to `has_unicode_cmap`, which reports them.
+        let loaded: Vec<_> = scan_install(|| {
+            fonts
+                .into_par_iter()
+                .filter_map(|(path, index)| {
+                    Some((path, index, load_unicode_cmap(path, index).ok()?))
+                })
+                .collect()
+        });
+        for (path, index, has_unicode_cmap) in loaded {
+            if let Some(font_data) = self.entry(path, index) {
+                font_data.has_unicode_cmap = Some(has_unicode_cmap);
+            }
+        }
+    }
+
+    fn get_fresh_font(&mut self, path: &str, index: isize) -> Option<&Font> {
+        self.get_fresh(path, index)?.font.as_ref()
+    }
+
+    /// Returns the entry of the face if the file did not change since it was
+    /// loaded.
+    fn get_fresh(&mut self, path: &str, index: isize) -> Option<&mut FontCacheFontData> {
+        let key = get_key(path, index);
+        if !self.data.fonts.get(&key)?.is_fresh(path) {
+            return None;
+        }
+
+        self.touch(&key);
+        self.data.fonts.get_mut(&key)
+    }
+
+    /// Returns the entry of the face, replacing it with an empty one if the
+    /// file changed.
+    fn entry(&mut self, path: &str, index: isize) -> Option<&mut FontCacheFontData> {
+        let key = get_key(path, index);
+        if self.get_fresh(path, index).is_none() {
+            self.insert(key.to_owned(), FontCacheFontData::new(path)?);
+        }
+        self.data.fonts.get_mut(&key)
+    }
+
+    fn insert(&mut self, key: String, font_data: FontCacheFontData) {
+        if let Some(old_font_data) = self.data.fonts.insert(key.to_owned(), font_data) {
+            self.recency.remove(&old_font_data.last_used);
+        }
+        self.touch(&key);
+
+        while self.data.fonts.len() > CAPACITY {
+            let Some((_, key)) = self.recency.pop_first() else {
+                break;
+            };
+            self.data.fonts.remove(&key);
+        }
+    }
+
+    /// Marks the font at `key` as the most recently used one.
+    fn touch(&mut self, key: &str) {
+        let Some(font_data) = self.data.fonts.get_mut(key) else {
+            return;
+        };
+        self.clock += 1;
+        self.recency.remove(&font_data.last_used);
+        font_data.last_used = self.clock;
+        self.recency.insert(self.clock, key.to_owned());
+    }
+
     pub fn read(&mut self) {
         self.data = self.try_read().unwrap_or_default();
+        self.recency.clear();
+        let keys: Vec<_> = self.data.fonts.keys().cloned().collect();
+        for key in keys {
+            self.touch(&key);
+        }
     }
 
-    pub fn write(&self) {
-        let _ = self.try_write(&self.data);
+    pub fn write(&mut self) {
+        (self.data.freetype_version, self.data.fontconfig_version) = current_versions().unzip();
+        if self.try_write(&self.data).is_none() {
+            debug!("failed to write {}", self.path.display());
+        }
     }
 
     fn try_read(&self) -> Option<FontCacheData> {
         let file = File::open(&self.path).ok()?;
         let data: FontCacheData = serde_json::from_reader(file).ok()?;
-        if data.version == VERSION {
+        // Without FreeType the versions cannot be compared, so nothing is
+        // reused.
+        let current = current_versions();
+        if data.version == VERSION
+            && current.is_some()
+            && data.freetype_version.zip(data.fontconfig_version) == current
+        {
             Some(data)
         } else {
+            debug!("discarding outdated {}", self.path.display());
             None
         }
     }
@@ -104,3 +287,146 @@ impl FontCache {
         serde_json::to_writer(file, data).ok()
     }
 }
+
+/// Returns the FreeType and fontconfig versions, or `None` if FreeType is not
+/// available.
+fn current_versions() -> Option<(Version, Version)> {
+    let library = FT_POOL.get().ok()?;
+    Some((library.version(), fontconfig::version()))
+}
+
+fn load_unicode_cmap(path: &str, index: isize) -> Result<bool, freetype::Error> {
+    let library = FT_POOL.get()?;
+    Ok(library
+        .face_from_file(path, index)
+        .is_ok_and(|face| face.has_unicode_cmap()))
+}
+
+fn get_key(path: &str, index: isize) -> String {
+    format!("{}:{}", path, index)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf, process, time::SystemTime};
+
+    use super::{FontCache, FontCacheFontData, CAPACITY};
+
+    const VARIABLE_FONT: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../tests/fonts/FixtureSansVariable.ttf"
+    ));
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("figma-agent-cache-{}-{name}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn skips_truncated_fonts() {
+        let dir = temp_dir("truncated");
+        let valid = dir.join("valid.ttf");
+        let truncated = dir.join("truncated.ttf");
+        fs::write(&valid, VARIABLE_FONT).unwrap();
+        // Cut inside the fvar table, so that head and the rest are missing.
+        fs::write(&truncated, &VARIABLE_FONT[..400]).unwrap();
+        let valid = valid.to_str().unwrap();
+        let truncated = truncated.to_str().unwrap();
+
+        let mut font_cache = FontCache::new(dir.join("fonts.json"));
+        assert!(font_cache.get(truncated, 0).is_none());
+        assert!(font_cache.get(valid, 0).is_some());
+
+        let mut font_cache = FontCache::new(dir.join("fonts.json"));
+        font_cache.prefetch([(truncated, 0), (valid, 0)]);
+        assert!(font_cache.get_fresh_font(truncated, 0).is_none());
+        let font = font_cache.get_fresh_font(valid, 0).unwrap();
+        assert_eq!(font.postscript_name, "FixtureSansVariable-Regular");
+        assert!(font_cache.get(truncated, 0).is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn discards_caches_without_library_versions() {
+        let dir = temp_dir("versions");
+        let valid = dir.join("valid.ttf");
+        fs::write(&valid, VARIABLE_FONT).unwrap();
+        let valid = valid.to_str().unwrap();
+        let cache_path = dir.join("fonts.json");
+
+        let mut font_cache = FontCache::new(&cache_path);
+        font_cache.get(valid, 0).unwrap();
+        font_cache.write();
+
+        let mut font_cache = FontCache::new(&cache_path);
+        font_cache.read();
+        assert!(font_cache.get_fresh_font(valid, 0).is_some());
+
+        let mut data: serde_json::Value =
+            serde_json::from_slice(&fs::read(&cache_path).unwrap()).unwrap();
+        data["freetypeVersion"] = serde_json::Value::Null;
+        fs::write(&cache_path, data.to_string()).unwrap();
+
+        let mut font_cache = FontCache::new(&cache_path);
+        font_cache.read();
+        assert!(font_cache.get_fresh_font(valid, 0).is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn evicts_the_least_recently_used_font() {
+        let font_data = || FontCacheFontData {
+            modified_time: SystemTime::UNIX_EPOCH,
+            size: 0,
+            font: None,
+            has_unicode_cmap: None,
+            last_used: 0,
+        };
+
+        let mut font_cache = FontCache::new("fonts.json");
+        for index in 0..CAPACITY {
+            font_cache.insert(index.to_string(), font_data());
+        }
+        font_cache.touch("0");
+        font_cache.insert(CAPACITY.to_string(), font_data());
+
+        assert_eq!(font_cache.data.fonts.len(), CAPACITY);
+        assert_eq!(font_cache.recency.len(), CAPACITY);
+        assert!(font_cache.data.fonts.contains_key("0"));
+        assert!(!font_cache.data.fonts.contains_key("1"));
+        assert!(font_cache.data.fonts.contains_key(&CAPACITY.to_string()));
+    }
+
+    #[test]
+    fn records_unicode_cmaps() {
+        let dir = temp_dir("cmaps");
+        let valid = dir.join("valid.ttf");
+        let truncated = dir.join("truncated.ttf");
+        fs::write(&valid, VARIABLE_FONT).unwrap();
+        fs::write(&truncated, &VARIABLE_FONT[..400]).unwrap();
+        let valid = valid.to_str().unwrap();
+        let truncated = truncated.to_str().unwrap();
+
+        let mut font_cache = FontCache::new(dir.join("fonts.json"));
+        font_cache.prefetch_unicode_cmaps([(valid, 0), (truncated, 0)]);
+        let has_unicode_cmap = |font_cache: &mut FontCache, path| {
+            font_cache
+                .get_fresh(path, 0)
+                .and_then(|font_data| font_data.has_unicode_cmap)
+        };
+        assert_eq!(has_unicode_cmap(&mut font_cache, valid), Some(true));
+        assert_eq!(has_unicode_cmap(&mut font_cache, truncated), Some(false));
+        assert!(font_cache.has_unicode_cmap(valid, 0).unwrap());
+
+        // Replacing the file invalidates the recorded result.
+        fs::write(valid, &VARIABLE_FONT[..400]).unwrap();
+        assert_eq!(has_unicode_cmap(&mut font_cache, valid), None);
+        assert!(!font_cache.has_unicode_cmap(valid, 0).unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
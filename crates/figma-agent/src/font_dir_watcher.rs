@@ -0,0 +1,215 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use inotify::{EventMask, Events, Inotify, WatchDescriptor, WatchMask, Watches};
+use parking_lot::Mutex;
+
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+const WATCH_MASK: WatchMask = WatchMask::CREATE
+    .union(WatchMask::DELETE)
+    .union(WatchMask::CLOSE_WRITE)
+    .union(WatchMask::MOVED_FROM)
+    .union(WatchMask::MOVED_TO);
+
+pub struct FontDirWatcher {
+    changed: Arc<AtomicBool>,
+    watched_dirs: Arc<Mutex<WatchedDirs>>,
+}
+
+impl FontDirWatcher {
+    pub fn new<I, P>(dirs: I) -> io::Result<FontDirWatcher>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let inotify = Inotify::init()?;
+        let watched_dirs = Arc::new(Mutex::new(WatchedDirs {
+            watches: inotify.watches(),
+            dirs: HashMap::new(),
+        }));
+
+        let changed = Arc::new(AtomicBool::new(false));
+        thread::spawn({
+            let changed = changed.clone();
+            let watched_dirs = watched_dirs.clone();
+            move || watch(inotify, &watched_dirs, &changed)
+        });
+
+        let font_dir_watcher = FontDirWatcher {
+            changed,
+            watched_dirs,
+        };
+        font_dir_watcher.watch_dirs(dirs);
+        Ok(font_dir_watcher)
+    }
+
+    /// Starts watching `dirs` in addition to the directories already
+    /// watched. Directories that do not exist are skipped.
+    pub fn watch_dirs<I, P>(&self, dirs: I)
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let mut watched_dirs = self.watched_dirs.lock();
+        for dir in dirs {
+            watched_dirs.add(dir.as_ref());
+        }
+    }
+
+    pub fn take_changed(&self) -> bool {
+        self.changed.swap(false, Ordering::AcqRel)
+    }
+}
+
+struct WatchedDirs {
+    watches: Watches,
+    dirs: HashMap<WatchDescriptor, PathBuf>,
+}
+
+impl WatchedDirs {
+    /// Watches the directory and, since they can appear before the watch is
+    /// added, any subdirectories it already contains.
+    fn add(&mut self, dir: &Path) {
+        let Ok(wd) = self.watches.add(dir, WATCH_MASK) else {
+            return;
+        };
+        self.dirs.insert(wd, dir.to_owned());
+        for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+            if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                self.add(&entry.path());
+            }
+        }
+    }
+
+    /// Starts watching directories created in or moved into a watched
+    /// directory.
+    fn update(&mut self, events: Events) {
+        let mut new_dirs = Vec::new();
+        for event in events {
+            if event.mask.contains(EventMask::IGNORED) {
+                self.dirs.remove(&event.wd);
+            } else if event.mask.contains(EventMask::ISDIR)
+                && event
+                    .mask
+                    .intersects(EventMask::CREATE | EventMask::MOVED_TO)
+            {
+                if let (Some(parent), Some(name)) = (self.dirs.get(&event.wd), event.name) {
+                    new_dirs.push(parent.join(name));
+                }
+            }
+        }
+        for dir in new_dirs {
+            self.add(&dir);
+        }
+    }
+}
+
+fn watch(mut inotify: Inotify, watched_dirs: &Mutex<WatchedDirs>, changed: &AtomicBool) {
+    let mut buffer = [0; 4096];
+    while let Ok(events) = inotify.read_events_blocking(&mut buffer) {
+        watched_dirs.lock().update(events);
+        // Wait for the burst of events to settle and drain it, so that
+        // installing many fonts at once triggers a single rebuild.
+        thread::sleep(DEBOUNCE);
+        while let Ok(events) = inotify.read_events(&mut buffer) {
+            watched_dirs.lock().update(events);
+        }
+        changed.store(true, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        path::PathBuf,
+        process, thread,
+        time::{Duration, Instant},
+    };
+
+    use super::{FontDirWatcher, DEBOUNCE};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("figma-agent-watcher-{}-{name}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn wait_changed(watcher: &FontDirWatcher) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if watcher.take_changed() {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        false
+    }
+
+    #[test]
+    fn reports_a_change_once() {
+        let dir = temp_dir("change");
+        let watcher = FontDirWatcher::new([&dir]).unwrap();
+        assert!(!watcher.take_changed());
+
+        fs::write(dir.join("a.ttf"), b"font").unwrap();
+        assert!(wait_changed(&watcher));
+        assert!(!watcher.take_changed());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn debounces_a_burst_of_events() {
+        let dir = temp_dir("burst");
+        let watcher = FontDirWatcher::new([&dir]).unwrap();
+
+        for i in 0..10 {
+            fs::write(dir.join(format!("{i}.ttf")), b"font").unwrap();
+        }
+        assert!(wait_changed(&watcher));
+        thread::sleep(DEBOUNCE * 2);
+        assert!(!watcher.take_changed());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn watches_new_subdirectories() {
+        let dir = temp_dir("subdir");
+        let watcher = FontDirWatcher::new([&dir]).unwrap();
+
+        fs::create_dir_all(dir.join("new/nested")).unwrap();
+        assert!(wait_changed(&watcher));
+
+        fs::write(dir.join("new/nested/a.ttf"), b"font").unwrap();
+        assert!(wait_changed(&watcher));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn watches_dirs_added_later() {
+        let dir = temp_dir("later");
+        let later_dir = temp_dir("later-added");
+        let watcher = FontDirWatcher::new([&dir]).unwrap();
+
+        watcher.watch_dirs([&later_dir]);
+        fs::write(later_dir.join("a.ttf"), b"font").unwrap();
+        assert!(wait_changed(&watcher));
+
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&later_dir).unwrap();
+    }
+}
@@ -1,5 +1,5 @@
 use fontconfig::{
-    FcWeightToOpenType, Pattern, FC_WIDTH_CONDENSED, FC_WIDTH_EXPANDED, FC_WIDTH_EXTRACONDENSED,
+    opentype_weight, Pattern, FC_WIDTH_CONDENSED, FC_WIDTH_EXPANDED, FC_WIDTH_EXTRACONDENSED,
     FC_WIDTH_EXTRAEXPANDED, FC_WIDTH_NORMAL, FC_WIDTH_SEMICONDENSED, FC_WIDTH_SEMIEXPANDED,
     FC_WIDTH_ULTRACONDENSED, FC_WIDTH_ULTRAEXPANDED,
 };
@@ -12,8 +12,7 @@ pub trait PatternHelpers {
 
 impl PatternHelpers for Pattern {
     fn os_weight_class(&self) -> Option<i32> {
-        let weight = self.weight()?;
-        Some(unsafe { FcWeightToOpenType(weight) })
+        self.weight().map(opentype_weight)
     }
 
     fn os_width_class(&self) -> Option<i32> {
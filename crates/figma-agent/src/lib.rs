@@ -1,26 +1,98 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, process, sync::OnceLock};
 
 use lazy_static::lazy_static;
+use log::{error, warn};
 use parking_lot::ReentrantMutex;
+use rayon::{ThreadPool, ThreadPoolBuilder};
 use xdg::BaseDirectories;
 
 mod config;
 mod font;
 mod font_cache;
+mod font_dir_watcher;
 mod helpers;
 
 pub use config::*;
 pub use font::*;
 pub use font_cache::*;
+pub use font_dir_watcher::*;
 pub use helpers::*;
 
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
 lazy_static! {
     pub static ref XDG_DIRS: BaseDirectories = BaseDirectories::with_prefix("figma-agent").unwrap();
-    pub static ref CONFIG: Config = Config::new();
     pub static ref FONT_CACHE: ReentrantMutex<RefCell<FontCache>> =
         ReentrantMutex::new(RefCell::new(FontCache::new(
             XDG_DIRS.place_cache_file("fonts.json").unwrap()
         )));
-    pub static ref FC: fontconfig::Config = fontconfig::init().unwrap();
-    pub static ref FT: freetype::Library = freetype::init().unwrap();
+    pub static ref FC: fontconfig::Config = {
+        let fc = fontconfig::init().unwrap_or_else(|error| {
+            error!("fontconfig is not available or misconfigured: {}", error);
+            process::exit(1);
+        });
+        add_app_font_dirs(&fc);
+        fc
+    };
+    pub static ref FONT_DIR_WATCHER: Option<FontDirWatcher> = FontDirWatcher::new(FC.font_dirs())
+        .map_err(|error| warn!("failed to watch font directories: {}", error))
+        .ok();
+    pub static ref FT_POOL: freetype::LibraryPool = freetype::LibraryPool::new();
+    pub static ref SCAN_POOL: Option<ThreadPool> = ThreadPoolBuilder::new()
+        .num_threads(config().scan_threads)
+        .build()
+        .map_err(|error| warn!("failed to create the scan thread pool: {}", error))
+        .ok();
+}
+
+/// Returns the configuration passed to `set_config`, or the one read from
+/// the environment if it was not called.
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        Config::new().unwrap_or_else(|error| {
+            warn!("{}, using the default configuration", error);
+            Config::default()
+        })
+    })
+}
+
+/// Replaces the configuration read from the environment. Fails and hands
+/// `config` back if the configuration was already used.
+pub fn set_config(config: Config) -> Result<(), Config> {
+    CONFIG.set(config)
+}
+
+/// Runs `op` on `SCAN_POOL`, or on the global rayon pool if it could not be
+/// created.
+pub fn scan_install<OP, R>(op: OP) -> R
+where
+    OP: FnOnce() -> R + Send,
+    R: Send,
+{
+    match SCAN_POOL.as_ref() {
+        Some(scan_pool) => scan_pool.install(op),
+        None => op(),
+    }
+}
+
+pub fn reload_fonts() -> bool {
+    if fontconfig::reinit() {
+        add_app_font_dirs(&FC);
+        // The new configuration can list directories that were not watched.
+        if let Some(font_dir_watcher) = FONT_DIR_WATCHER.as_ref() {
+            font_dir_watcher.watch_dirs(FC.font_dirs());
+        }
+        true
+    } else {
+        warn!("failed to reinitialize fontconfig");
+        false
+    }
+}
+
+fn add_app_font_dirs(fc: &fontconfig::Config) {
+    for font_dir in &config().font_dirs {
+        if !fc.add_app_font_dir(font_dir) {
+            warn!("failed to add font directory {}", font_dir.display());
+        }
+    }
 }
@@ -1,43 +1,106 @@
-use std::io;
+use std::{env, io, process};
 
 use actix_cors::Cors;
 use actix_web::{guard, middleware::Logger, web, App, HttpServer};
 use env_logger::Env;
+use figma_agent::{Config, FC, FONT_CACHE};
 use listenfd::ListenFd;
+use log::{error, info};
 
 mod payload;
 mod routes;
 
-static ADDR: &str = "localhost:18412";
-static ORIGIN: &str = "https://www.figma.com";
+// The desktop app loads the web app, so both send this origin.
+static ORIGINS: &[&str] = &["https://www.figma.com"];
 
 #[actix_web::main]
 async fn main() -> io::Result<()> {
     env_logger::init_from_env(Env::default().default_filter_or("info"));
 
+    if figma_agent::set_config(parse_args()).is_err() {
+        error!("the configuration was used before the arguments were parsed");
+        process::exit(1);
+    }
+    let config = figma_agent::config();
+    // Fail at startup rather than on the first request.
+    lazy_static::initialize(&FC);
+    FONT_CACHE.lock().borrow_mut().read();
     let mut listen_fd = ListenFd::from_env();
 
     let server = HttpServer::new(|| {
         App::new()
             .wrap(Logger::default())
             .wrap(
-                Cors::default()
-                    .allowed_origin(ORIGIN)
+                ORIGINS
+                    .iter()
+                    .fold(Cors::default(), |cors, origin| cors.allowed_origin(origin))
+                    .allowed_methods(["GET"])
                     .allow_private_network_access(),
             )
             .service(
                 web::scope("/figma")
-                    .guard(guard::Header("Origin", ORIGIN))
+                    .guard(guard::fn_guard(|ctx| {
+                        ctx.head()
+                            .headers()
+                            .get("Origin")
+                            .is_some_and(|origin| ORIGINS.iter().any(|&item| origin == item))
+                    }))
                     .service(routes::font_files)
                     .service(routes::font_file),
             )
+            .service(routes::health)
+            .service(routes::version)
     });
 
     let server = if let Some(listener) = listen_fd.take_tcp_listener(0)? {
+        info!("listening on inherited socket {}", listener.local_addr()?);
         server.listen(listener)?
     } else {
-        server.bind(ADDR)?
+        info!("listening on {}:{}", config.host, config.port);
+        match server.bind((config.host.as_str(), config.port)) {
+            Ok(server) => server,
+            Err(error) => {
+                error!(
+                    "failed to listen on {}:{}: {}",
+                    config.host, config.port, error
+                );
+                process::exit(1);
+            }
+        }
     };
 
     server.workers(1).run().await
 }
+
+fn parse_args() -> Config {
+    let mut config = Config::new().unwrap_or_else(|error| usage(&error));
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--host" => {
+                config.host = args
+                    .next()
+                    .unwrap_or_else(|| usage("missing value for --host"))
+            }
+            "--port" => {
+                let value = args
+                    .next()
+                    .unwrap_or_else(|| usage("missing value for --port"));
+                config.port = value
+                    .parse()
+                    .unwrap_or_else(|_| usage(&format!("invalid port: {}", value)));
+            }
+            "--require-unicode" => config.require_unicode = true,
+            _ => usage(&format!("unexpected argument: {}", arg)),
+        }
+    }
+
+    config
+}
+
+fn usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("usage: figma-agent [--host <host>] [--port <port>] [--require-unicode]");
+    process::exit(2);
+}
@@ -43,3 +43,10 @@ pub struct VariationAxis {
     pub default: f64,
     pub hidden: bool,
 }
+
+#[derive(Serialize)]
+pub struct VersionResult {
+    pub version: String,
+    pub freetype: Option<String>,
+    pub fontconfig: String,
+}
@@ -1,5 +1,9 @@
 mod font_file;
 mod font_files;
+mod health;
+mod version;
 
 pub use font_file::*;
 pub use font_files::*;
+pub use health::*;
+pub use version::*;
@@ -1,7 +1,12 @@
-use std::path::PathBuf;
+use std::{
+    fs::{self, File},
+    io,
+    os::unix::fs::FileExt,
+    path::{Component, Path, PathBuf},
+};
 
 use actix_files::NamedFile;
-use actix_web::{error, get, web, Responder};
+use actix_web::{error, get, http::header, web, Responder};
 use figma_agent::FC;
 use serde::Deserialize;
 
@@ -12,14 +17,118 @@ pub struct Query {
 
 #[get("/font-file")]
 pub async fn font_file(query: web::Query<Query>) -> impl Responder {
-    let is_valid = FC
-        .font_dirs()
-        .flatten()
-        .any(|dir| query.file.starts_with(dir));
-
-    if is_valid {
-        Ok(NamedFile::open(&query.file))
-    } else {
-        Err(error::ErrorNotFound("Not Found"))
+    let query = query.into_inner();
+    let opened = web::block(move || {
+        resolve_font_path(&query.file, FC.font_dirs())
+            .map(|path| open_font_file(&path))
+            .transpose()
+    })
+    .await??;
+    let Some((file, content_type)) = opened else {
+        return Err(error::ErrorForbidden("Forbidden"));
+    };
+
+    let file = file.customize();
+    Ok(match content_type {
+        Some(content_type) => file.insert_header((header::CONTENT_TYPE, content_type)),
+        None => file,
+    })
+}
+
+/// Returns the resolved path if the file exists inside one of the font
+/// directories. Missing files are rejected the same way, so that requests
+/// cannot probe for files elsewhere.
+fn resolve_font_path<I, P>(file: &Path, font_dirs: I) -> Option<PathBuf>
+where
+    I: IntoIterator<Item = P>,
+    P: AsRef<Path>,
+{
+    if !file.is_absolute()
+        || file
+            .components()
+            .any(|component| component == Component::ParentDir)
+    {
+        return None;
+    }
+
+    // Compare resolved paths so that symlinks cannot escape the font
+    // directories and symlinked directories still match.
+    let path = fs::canonicalize(file).ok()?;
+    font_dirs
+        .into_iter()
+        .filter_map(|dir| fs::canonicalize(dir).ok())
+        .any(|dir| path.starts_with(dir))
+        .then_some(path)
+}
+
+fn open_font_file(path: &Path) -> io::Result<(NamedFile, Option<&'static str>)> {
+    let file = NamedFile::open(path)?;
+    let content_type = get_content_type(file.file());
+    Ok((file, content_type))
+}
+
+fn get_content_type(file: &File) -> Option<&'static str> {
+    // Read at an explicit offset so the position NamedFile streams from is
+    // left untouched.
+    let mut magic = [0; 4];
+    file.read_exact_at(&mut magic, 0).ok()?;
+
+    match &magic {
+        b"\x00\x01\x00\x00" | b"true" => Some("font/ttf"),
+        b"OTTO" => Some("font/otf"),
+        b"ttcf" => Some("font/collection"),
+        b"wOFF" => Some("font/woff"),
+        b"wOF2" => Some("font/woff2"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, os::unix::fs::symlink, process};
+
+    use super::resolve_font_path;
+
+    #[test]
+    fn resolves_symlinks_before_checking_font_dirs() {
+        let dir = env::temp_dir().join(format!("figma-agent-font-file-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let font_dir = dir.join("fonts");
+        let outside_dir = dir.join("outside");
+        fs::create_dir_all(&font_dir).unwrap();
+        fs::create_dir_all(&outside_dir).unwrap();
+        fs::write(font_dir.join("a.ttf"), b"font").unwrap();
+        fs::write(outside_dir.join("secret"), b"secret").unwrap();
+        symlink(font_dir.join("a.ttf"), font_dir.join("link.ttf")).unwrap();
+        symlink(outside_dir.join("secret"), font_dir.join("escape.ttf")).unwrap();
+        let font_dirs = [&font_dir];
+
+        let resolved = fs::canonicalize(font_dir.join("a.ttf")).unwrap();
+        assert_eq!(
+            resolve_font_path(&font_dir.join("a.ttf"), font_dirs),
+            Some(resolved.to_owned())
+        );
+        assert_eq!(
+            resolve_font_path(&font_dir.join("link.ttf"), font_dirs),
+            Some(resolved)
+        );
+        assert_eq!(
+            resolve_font_path(&font_dir.join("escape.ttf"), font_dirs),
+            None
+        );
+        assert_eq!(
+            resolve_font_path(&outside_dir.join("secret"), font_dirs),
+            None
+        );
+        assert_eq!(
+            resolve_font_path(&font_dir.join("missing.ttf"), font_dirs),
+            None
+        );
+        assert_eq!(
+            resolve_font_path(&font_dir.join("../outside/secret"), font_dirs),
+            None
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
     }
 }
@@ -1,25 +1,97 @@
-use std::{collections::HashMap, fs, time::UNIX_EPOCH};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fs,
+    time::UNIX_EPOCH,
+};
 
 use actix_web::{get, web, Responder};
-use figma_agent::{PatternHelpers, CONFIG, FC, FONT_CACHE};
+use figma_agent::{config, reload_fonts, PatternHelpers, FC, FONT_CACHE, FONT_DIR_WATCHER};
 use fontconfig::{Pattern, FC_SLANT_ROMAN};
+use freetype::fixed_to_f64;
 use itertools::Itertools;
+use lazy_static::lazy_static;
+use log::{debug, info, warn};
+use parking_lot::RwLock;
 
 use crate::payload;
 
+lazy_static! {
+    static ref FONT_FILES: RwLock<Option<payload::FontFilesResult>> = RwLock::new(None);
+}
+
 #[get("/font-files")]
 pub async fn font_files() -> impl Responder {
+    let Some(font_dir_watcher) = FONT_DIR_WATCHER.as_ref() else {
+        return web::Json(get_font_files());
+    };
+
+    if font_dir_watcher.take_changed() {
+        info!("font directories changed, reloading fonts");
+        reload_fonts();
+        *FONT_FILES.write() = None;
+    }
+
+    if let Some(result) = FONT_FILES.read().as_ref() {
+        return web::Json(result.to_owned());
+    }
+
+    let result = get_font_files();
+    *FONT_FILES.write() = Some(result.to_owned());
+    web::Json(result)
+}
+
+fn get_font_files() -> payload::FontFilesResult {
     let font_cache = FONT_CACHE.lock();
-    font_cache.borrow_mut().read();
+
+    for font_dir in FC.font_dirs() {
+        debug!("scanning {}", font_dir.display());
+    }
 
     let patterns: Vec<_> = FC.list_fonts(&Pattern::new(), None).iter().collect();
+    let mut entries = dedup_font_files(patterns.iter().flat_map(get_font_file).collect());
+    if config().require_unicode {
+        let mut font_cache = font_cache.borrow_mut();
+        font_cache.prefetch_unicode_cmaps(
+            entries
+                .iter()
+                .map(|item| (item.path.as_str(), item.index as isize & 0xFFFF)),
+        );
+        // Without FreeType nothing can be checked, so fonts are kept rather
+        // than emptying the whole list.
+        let mut unchecked = None;
+        entries.retain(|item| {
+            match font_cache.has_unicode_cmap(&item.path, item.index as isize & 0xFFFF) {
+                Ok(has_unicode_cmap) => has_unicode_cmap,
+                Err(error) => {
+                    unchecked.get_or_insert(error);
+                    true
+                }
+            }
+        });
+        if let Some(error) = unchecked {
+            warn!("failed to check font charmaps: {}", error);
+        }
+    }
+    debug!(
+        "skipped {} of {} fonts",
+        patterns.len() - entries.len(),
+        patterns.len()
+    );
+
+    if config().enable_variable_font {
+        font_cache.borrow_mut().prefetch(
+            entries
+                .iter()
+                .filter(|item| item.is_variable)
+                .map(|item| (item.path.as_str(), item.index as isize & 0xFFFF)),
+        );
+    }
 
-    let font_files = patterns
-        .iter()
-        .flat_map(get_font_file)
+    let groups = entries
+        .into_iter()
         .into_group_map_by(|item| item.path.to_owned());
 
-    let font_files: HashMap<_, _> = font_files
+    let files: HashMap<_, _> = groups
         .into_iter()
         .map(|(path, items)| {
             if items.iter().any(|item| item.is_variable) {
@@ -39,15 +111,51 @@ pub async fn font_files() -> impl Responder {
 
     font_cache.borrow_mut().write();
 
-    web::Json(payload::FontFilesResult {
+    info!(
+        "found {} fonts in {} files",
+        files.values().map(Vec::len).sum::<usize>(),
+        files.len()
+    );
+
+    payload::FontFilesResult {
         version: 22,
         package: "116.10.8".to_owned(), // latest version as of 2023-06-22
-        font_files,
-    })
+        font_files: files,
+    }
+}
+
+/// The same face is listed twice when it is reachable through a symlink or
+/// when a directory is both configured and added as an app font directory.
+/// Keeps the record with the most names of each face, in listing order.
+fn dedup_font_files(entries: Vec<payload::FontFile>) -> Vec<payload::FontFile> {
+    let completeness = |font_file: &payload::FontFile| {
+        [&font_file.postscript, &font_file.family, &font_file.style]
+            .into_iter()
+            .filter(|name| !name.is_empty())
+            .count()
+    };
+
+    let mut result: Vec<payload::FontFile> = Vec::new();
+    let mut positions = HashMap::new();
+    for font_file in entries {
+        match positions.entry((font_file.path.to_owned(), font_file.index)) {
+            Entry::Occupied(entry) => {
+                let existing = &mut result[*entry.get()];
+                if completeness(&font_file) > completeness(existing) {
+                    *existing = font_file;
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(result.len());
+                result.push(font_file);
+            }
+        }
+    }
+    result
 }
 
 fn get_font_file(pattern: &Pattern) -> Option<payload::FontFile> {
-    let path = pattern.file()?;
+    let path = canonicalize(pattern.file()?);
     let index = pattern.index()?;
 
     Some(payload::FontFile {
@@ -55,7 +163,7 @@ fn get_font_file(pattern: &Pattern) -> Option<payload::FontFile> {
         index,
 
         user_installed: true,
-        modified_at: fs::metadata(path)
+        modified_at: fs::metadata(&path)
             .and_then(|metadata| metadata.modified())
             .ok()
             .and_then(|modified_time| modified_time.duration_since(UNIX_EPOCH).ok())
@@ -77,8 +185,15 @@ fn get_font_file(pattern: &Pattern) -> Option<payload::FontFile> {
     })
 }
 
+fn canonicalize(path: &str) -> String {
+    fs::canonicalize(path)
+        .ok()
+        .and_then(|path| path.to_str().map(ToOwned::to_owned))
+        .unwrap_or_else(|| path.to_owned())
+}
+
 fn get_variable_font_file(font_file: &payload::FontFile) -> Option<payload::FontFile> {
-    if !CONFIG.enable_variable_font {
+    if !config().enable_variable_font {
         return None;
     }
 
@@ -106,8 +221,6 @@ fn get_variable_font_file(font_file: &payload::FontFile) -> Option<payload::Font
         font_file.style = font.style_name.to_owned();
     }
 
-    let from_fixed = |fixed| fixed as f64 / 65536.0;
-
     font_file.variation_axes = Some(
         font.variation_axes
             .iter()
@@ -115,14 +228,15 @@ fn get_variable_font_file(font_file: &payload::FontFile) -> Option<payload::Font
             .map(|(index, variation_axis)| payload::VariationAxis {
                 name: variation_axis.name.to_owned(),
                 tag: variation_axis.tag.to_owned(),
-                value: from_fixed(
+                value: fixed_to_f64(
                     instance
                         .map(|instance| instance.coordinates[index])
-                        .unwrap_or(variation_axis.default),
+                        .unwrap_or(variation_axis.default)
+                        .into(),
                 ),
-                min: from_fixed(variation_axis.min),
-                max: from_fixed(variation_axis.max),
-                default: from_fixed(variation_axis.default),
+                min: fixed_to_f64(variation_axis.min.into()),
+                max: fixed_to_f64(variation_axis.max.into()),
+                default: fixed_to_f64(variation_axis.default.into()),
                 hidden: variation_axis.is_hidden,
             })
             .collect(),
@@ -130,3 +244,93 @@ fn get_variable_font_file(font_file: &payload::FontFile) -> Option<payload::Font
 
     Some(font_file)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, env, fs, os::unix::fs::symlink, path::Path, process};
+
+    use fontconfig::Pattern;
+
+    use super::{dedup_font_files, get_font_file};
+    use crate::payload;
+
+    fn font_file(path: &str, index: i32, postscript: &str, style: &str) -> payload::FontFile {
+        payload::FontFile {
+            path: path.to_owned(),
+            index,
+            user_installed: true,
+            modified_at: 0,
+            postscript: postscript.to_owned(),
+            family: "Fixture Sans".to_owned(),
+            style: style.to_owned(),
+            weight: 400,
+            italic: false,
+            stretch: 5,
+            is_variable: false,
+            variation_axes: None,
+        }
+    }
+
+    #[test]
+    fn keeps_the_most_complete_duplicate() {
+        let font_files = dedup_font_files(vec![
+            font_file("/fonts/a.ttf", 0, "", ""),
+            font_file("/fonts/a.ttf", 1, "FixtureSans-Bold", "Bold"),
+            font_file("/fonts/a.ttf", 0, "FixtureSans-Regular", "Regular"),
+            font_file("/fonts/a.ttf", 0, "FixtureSans-Other", ""),
+            font_file("/fonts/b.ttf", 0, "FixtureSans-Regular", "Regular"),
+        ]);
+
+        let keys: Vec<_> = font_files
+            .iter()
+            .map(|font_file| {
+                (
+                    font_file.path.as_str(),
+                    font_file.index,
+                    font_file.postscript.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                ("/fonts/a.ttf", 0, "FixtureSans-Regular"),
+                ("/fonts/a.ttf", 1, "FixtureSans-Bold"),
+                ("/fonts/b.ttf", 0, "FixtureSans-Regular"),
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_each_face_once_when_a_directory_is_added_twice() {
+        let font_dir =
+            fs::canonicalize(concat!(env!("CARGO_MANIFEST_DIR"), "/../../tests/fonts")).unwrap();
+        let dir = env::temp_dir().join(format!("figma-agent-font-files-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let link = dir.join("fonts");
+        symlink(&font_dir, &link).unwrap();
+
+        let fc = fontconfig::Config::new();
+        assert!(fc.add_app_font_dir(&font_dir));
+        assert!(fc.add_app_font_dir(&link));
+        let patterns: Vec<_> = fc.list_fonts(&Pattern::new(), None).iter().collect();
+        let listed: Vec<_> = patterns.iter().flat_map(get_font_file).collect();
+        let font_files = dedup_font_files(listed.to_vec());
+
+        let keys: HashSet<_> = listed
+            .iter()
+            .map(|font_file| (font_file.path.as_str(), font_file.index))
+            .collect();
+        assert!(listed.len() > keys.len());
+        assert_eq!(font_files.len(), keys.len());
+        for font_file in &font_files {
+            assert!(Path::new(&font_file.path).starts_with(&font_dir));
+            assert!(!font_file.postscript.is_empty());
+            assert!(!font_file.family.is_empty());
+            assert!(!font_file.style.is_empty());
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
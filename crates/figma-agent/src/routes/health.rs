@@ -0,0 +1,6 @@
+use actix_web::{get, Responder};
+
+#[get("/health")]
+pub async fn health() -> impl Responder {
+    "OK"
+}
@@ -0,0 +1,19 @@
+use actix_web::{get, web, Responder};
+use figma_agent::FT_POOL;
+
+use crate::payload;
+
+#[get("/version")]
+pub async fn version() -> impl Responder {
+    let freetype = FT_POOL.get().ok().map(|library| {
+        let (major, minor, patch) = library.version();
+        format!("{}.{}.{}", major, minor, patch)
+    });
+    let (major, minor, revision) = fontconfig::version();
+
+    web::Json(payload::VersionResult {
+        version: env!("CARGO_PKG_VERSION").to_owned(),
+        freetype,
+        fontconfig: format!("{}.{}.{}", major, minor, revision),
+    })
+}
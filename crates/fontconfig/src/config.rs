@@ -1,10 +1,15 @@
 #![allow(clippy::missing_safety_doc)]
 
-use std::ptr;
+use std::{ffi::CString, os::unix::ffi::OsStrExt, path::Path, ptr};
 
-use fontconfig_sys::{FcConfig, FcConfigCreate, FcConfigDestroy, FcConfigGetFontDirs, FcFontList};
+use fontconfig_sys::{
+    FcConfig, FcConfigAppFontAddDir, FcConfigAppFontAddFile, FcConfigAppFontClear, FcConfigCreate,
+    FcConfigDestroy, FcConfigGetFontDirs, FcConfigSubstitute, FcFalse, FcFontList, FcFontMatch,
+    FcFontSort, FcInitLoadConfigAndFonts, FcMatchKind, FcResultMatch, FcTrue, FC_FAMILY, FC_FILE,
+    FC_INDEX, FC_SLANT, FC_STYLE, FC_VARIABLE, FC_WEIGHT, FC_WIDTH,
+};
 
-use crate::{FontSet, ObjectSet, Pattern, StrList};
+use crate::{Error, FontDirIter, FontInfo, FontSet, ObjectSet, Pattern};
 
 pub struct Config {
     raw: *mut FcConfig,
@@ -32,14 +37,45 @@ impl Config {
         Config { raw }
     }
 
+    pub fn init_load_config_and_fonts() -> Result<Config, Error> {
+        let raw = unsafe { FcInitLoadConfigAndFonts() };
+        if !raw.is_null() {
+            Ok(Config { raw })
+        } else {
+            Err(Error::InitFailed)
+        }
+    }
+
     pub unsafe fn from_raw(raw: *mut FcConfig) -> Config {
         Config { raw }
     }
 
-    pub fn font_dirs(&self) -> StrList {
+    pub fn font_dirs(&self) -> FontDirIter {
         let raw_str_list = unsafe { FcConfigGetFontDirs(self.raw) };
         assert!(!raw_str_list.is_null());
-        unsafe { StrList::from_raw(raw_str_list) }
+        unsafe { FontDirIter::from_raw(raw_str_list) }
+    }
+
+    pub fn add_app_font_file(&self, path: &Path) -> bool {
+        if let Ok(path) = CString::new(path.as_os_str().as_bytes()) {
+            let result = unsafe { FcConfigAppFontAddFile(self.raw, path.as_ptr() as _) };
+            result != FcFalse
+        } else {
+            false
+        }
+    }
+
+    pub fn add_app_font_dir(&self, path: &Path) -> bool {
+        if let Ok(path) = CString::new(path.as_os_str().as_bytes()) {
+            let result = unsafe { FcConfigAppFontAddDir(self.raw, path.as_ptr() as _) };
+            result != FcFalse
+        } else {
+            false
+        }
+    }
+
+    pub fn clear_app_fonts(&self) {
+        unsafe { FcConfigAppFontClear(self.raw) };
     }
 
     pub fn list_fonts(&self, pattern: &Pattern, object_set: Option<&ObjectSet>) -> FontSet {
@@ -55,6 +91,57 @@ impl Config {
         assert!(!raw_font_set.is_null());
         unsafe { FontSet::from_raw(raw_font_set) }
     }
+
+    pub fn fonts(&self) -> impl Iterator<Item = FontInfo> {
+        let objects: [&[u8]; 8] = [
+            FC_FILE,
+            FC_INDEX,
+            FC_FAMILY,
+            FC_STYLE,
+            FC_WEIGHT,
+            FC_SLANT,
+            FC_WIDTH,
+            FC_VARIABLE,
+        ];
+        let mut object_set = ObjectSet::new();
+        for object in objects {
+            object_set.insert(object);
+        }
+
+        let font_set = self.list_fonts(&Pattern::new(), Some(&object_set));
+        let fonts: Vec<_> = font_set
+            .iter()
+            .filter_map(|pattern| FontInfo::from_pattern(&pattern))
+            .collect();
+        fonts.into_iter()
+    }
+
+    pub fn substitute(&self, pattern: &mut Pattern, kind: FcMatchKind) -> bool {
+        let result = unsafe { FcConfigSubstitute(self.raw, pattern.raw(), kind) };
+        result != FcFalse
+    }
+
+    pub fn match_font(&self, pattern: &Pattern) -> Result<Pattern, Error> {
+        let mut result = FcResultMatch;
+        let raw_pattern = unsafe { FcFontMatch(self.raw, pattern.raw(), &mut result) };
+        if !raw_pattern.is_null() {
+            Ok(unsafe { Pattern::from_raw(raw_pattern) })
+        } else {
+            Err(result.into())
+        }
+    }
+
+    pub fn sort_fonts(&self, pattern: &Pattern, trim: bool) -> Result<FontSet, Error> {
+        let mut result = FcResultMatch;
+        let trim = if trim { FcTrue } else { FcFalse };
+        let raw_font_set =
+            unsafe { FcFontSort(self.raw, pattern.raw(), trim, ptr::null_mut(), &mut result) };
+        if !raw_font_set.is_null() {
+            Ok(unsafe { FontSet::from_raw(raw_font_set) })
+        } else {
+            Err(result.into())
+        }
+    }
 }
 
 impl Drop for Config {
@@ -64,3 +151,25 @@ impl Drop for Config {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_every_face_of_a_collection() {
+        let config = Config::new();
+        let path = Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../tests/fonts/FixtureSans.ttc"
+        ));
+        assert!(config.add_app_font_file(path));
+
+        let mut faces: Vec<_> = config
+            .fonts()
+            .map(|font| (font.index, font.style.unwrap_or_default()))
+            .collect();
+        faces.sort();
+        assert_eq!(faces, [(0, "Regular".to_owned()), (1, "Bold".to_owned())]);
+    }
+}
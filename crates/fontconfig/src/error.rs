@@ -0,0 +1,45 @@
+use std::{error, fmt};
+
+use fontconfig_sys::{
+    FcResult, FcResultNoId, FcResultNoMatch, FcResultOutOfMemory, FcResultTypeMismatch,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    NoMatch,
+    TypeMismatch,
+    NoId,
+    InvalidUtf8,
+    OutOfMemory,
+    InitFailed,
+    Other(FcResult),
+}
+
+impl From<FcResult> for Error {
+    #[allow(non_upper_case_globals)]
+    fn from(result: FcResult) -> Self {
+        match result {
+            FcResultNoMatch => Error::NoMatch,
+            FcResultTypeMismatch => Error::TypeMismatch,
+            FcResultNoId => Error::NoId,
+            FcResultOutOfMemory => Error::OutOfMemory,
+            result => Error::Other(result),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoMatch => f.write_str("no such object"),
+            Error::TypeMismatch => f.write_str("object has a different type"),
+            Error::NoId => f.write_str("no value at this index"),
+            Error::InvalidUtf8 => f.write_str("string value is not valid UTF-8"),
+            Error::OutOfMemory => f.write_str("out of memory"),
+            Error::InitFailed => f.write_str("failed to load the fontconfig configuration"),
+            Error::Other(result) => write!(f, "fontconfig result {}", result),
+        }
+    }
+}
+
+impl error::Error for Error {}
@@ -0,0 +1,29 @@
+#![allow(clippy::missing_safety_doc)]
+
+use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::Path};
+
+use fontconfig_sys::FcStrList;
+
+use crate::StrList;
+
+pub struct FontDirIter<'a> {
+    list: StrList<'a>,
+}
+
+impl<'a> FontDirIter<'a> {
+    pub unsafe fn from_raw(raw: *mut FcStrList) -> FontDirIter<'a> {
+        FontDirIter {
+            list: StrList::from_raw(raw),
+        }
+    }
+}
+
+impl<'a> Iterator for FontDirIter<'a> {
+    type Item = &'a Path;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list
+            .next_bytes()
+            .map(|bytes| Path::new(OsStr::from_bytes(bytes)))
+    }
+}
@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+
+use crate::Pattern;
+
+#[derive(Debug, Clone)]
+pub struct FontInfo {
+    pub path: PathBuf,
+    pub index: i32,
+    pub family: Option<String>,
+    pub style: Option<String>,
+    pub weight: Option<i32>,
+    pub slant: Option<i32>,
+    pub width: Option<i32>,
+    pub variable: bool,
+}
+
+impl FontInfo {
+    pub fn from_pattern(pattern: &Pattern) -> Option<FontInfo> {
+        Some(FontInfo {
+            path: pattern.file_path()?.to_owned(),
+            index: pattern.index().unwrap_or(0),
+            family: pattern.family().map(ToOwned::to_owned),
+            style: pattern.style().map(ToOwned::to_owned),
+            weight: pattern.weight(),
+            slant: pattern.slant(),
+            width: pattern.width(),
+            variable: pattern.is_variable().unwrap_or(false),
+        })
+    }
+}
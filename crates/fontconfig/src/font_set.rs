@@ -33,8 +33,20 @@ impl FontSet {
         FontSet { raw }
     }
 
+    pub fn len(&self) -> usize {
+        unsafe { (*self.raw).nfont as _ }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = Pattern> {
-        let slice = unsafe { slice::from_raw_parts((*self.raw).fonts, (*self.raw).nfont as _) };
+        let slice = if !self.is_empty() {
+            unsafe { slice::from_raw_parts((*self.raw).fonts, self.len()) }
+        } else {
+            &[]
+        };
         slice.iter().map(|&raw_pattern| unsafe {
             FcPatternReference(raw_pattern);
             Pattern::from_raw(raw_pattern)
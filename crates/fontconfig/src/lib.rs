@@ -3,6 +3,9 @@ use std::ptr;
 pub use fontconfig_sys::*;
 
 mod config;
+mod error;
+mod font_dir_iter;
+mod font_info;
 mod font_set;
 mod object_set;
 mod pattern;
@@ -10,17 +13,54 @@ mod str_list;
 mod str_set;
 
 pub use crate::config::*;
+pub use crate::error::*;
+pub use crate::font_dir_iter::*;
+pub use crate::font_info::*;
 pub use crate::font_set::*;
 pub use crate::object_set::*;
 pub use crate::pattern::*;
 pub use crate::str_list::*;
 pub use crate::str_set::*;
 
-pub fn init() -> Option<Config> {
+pub fn init() -> Result<Config, Error> {
     let result = unsafe { FcInit() };
     if result != FcFalse {
-        Some(unsafe { Config::from_raw(ptr::null_mut()) })
+        Ok(unsafe { Config::from_raw(ptr::null_mut()) })
     } else {
-        None
+        Err(Error::InitFailed)
     }
 }
+
+pub fn reinit() -> bool {
+    let result = unsafe { FcInitReinitialize() };
+    result != FcFalse
+}
+
+/// Converts an OpenType `usWeightClass` to a fontconfig weight (0–210).
+pub fn weight_from_opentype(ot_weight: i32) -> i32 {
+    unsafe { FcWeightFromOpenType(ot_weight) }
+}
+
+pub fn weight_from_opentype_double(ot_weight: f64) -> f64 {
+    unsafe { FcWeightFromOpenTypeDouble(ot_weight) }
+}
+
+/// Converts a fontconfig weight (0–210) to an OpenType `usWeightClass`.
+pub fn weight_to_opentype(fc_weight: i32) -> i32 {
+    unsafe { FcWeightToOpenType(fc_weight) }
+}
+
+pub fn weight_to_opentype_double(fc_weight: f64) -> f64 {
+    unsafe { FcWeightToOpenTypeDouble(fc_weight) }
+}
+
+/// Same as [`weight_to_opentype`].
+pub fn opentype_weight(fc_weight: i32) -> i32 {
+    weight_to_opentype(fc_weight)
+}
+
+/// Runtime fontconfig version as `(major, minor, revision)`.
+pub fn version() -> (i32, i32, i32) {
+    let version = unsafe { FcGetVersion() };
+    (version / 10000, version / 100 % 100, version % 100)
+}
@@ -1,14 +1,24 @@
 #![allow(clippy::missing_safety_doc)]
 
-use std::{ffi::CStr, ptr};
+use std::{
+    ffi::{CStr, CString, OsStr},
+    os::unix::ffi::OsStrExt,
+    path::Path,
+    ptr,
+};
 
 use fontconfig_sys::{
-    FcFalse, FcPattern, FcPatternCreate, FcPatternDestroy, FcPatternGetBool, FcPatternGetDouble,
-    FcPatternGetFTFace, FcPatternGetInteger, FcPatternGetString, FcResultMatch, FC_FAMILY, FC_FILE,
-    FC_FT_FACE, FC_FULLNAME, FC_INDEX, FC_POSTSCRIPT_NAME, FC_SLANT, FC_STYLE, FC_VARIABLE,
-    FC_WEIGHT, FC_WIDTH,
+    FcDefaultSubstitute, FcFalse, FcFreeTypeQueryFace, FcLangSetGetLangs, FcNameParse,
+    FcNameUnparse, FcPattern, FcPatternAddBool, FcPatternAddDouble, FcPatternAddInteger,
+    FcPatternAddString, FcPatternCreate, FcPatternDel, FcPatternDestroy, FcPatternGetBool,
+    FcPatternGetDouble, FcPatternGetFTFace, FcPatternGetInteger, FcPatternGetLangSet,
+    FcPatternGetString, FcResultMatch, FcStrFree, FcTrue, FC_FAMILY, FC_FILE, FC_FT_FACE,
+    FC_FULLNAME, FC_INDEX, FC_LANG, FC_POSTSCRIPT_NAME, FC_SLANT, FC_STYLE, FC_VARIABLE, FC_WEIGHT,
+    FC_WIDTH,
 };
 
+use crate::{Error, StrSet};
+
 pub struct Pattern {
     raw: *mut FcPattern,
 }
@@ -32,6 +42,34 @@ impl Pattern {
         Pattern { raw }
     }
 
+    pub fn from_freetype_face<P>(face: &freetype::Face, file: P, id: u32) -> Option<Pattern>
+    where
+        P: AsRef<str>,
+    {
+        let file = CString::new(file.as_ref()).ok()?;
+        let raw =
+            unsafe { FcFreeTypeQueryFace(face.raw(), file.as_ptr() as _, id, ptr::null_mut()) };
+        if !raw.is_null() {
+            Some(Pattern { raw })
+        } else {
+            None
+        }
+    }
+
+    /// Parses a pattern from its textual form, e.g. `"Inter:weight=200"`.
+    pub fn parse<S>(name: S) -> Option<Pattern>
+    where
+        S: AsRef<str>,
+    {
+        let name = CString::new(name.as_ref()).ok()?;
+        let raw = unsafe { FcNameParse(name.as_ptr() as _) };
+        if !raw.is_null() {
+            Some(Pattern { raw })
+        } else {
+            None
+        }
+    }
+
     pub unsafe fn from_raw(raw: *mut FcPattern) -> Pattern {
         Pattern { raw }
     }
@@ -44,133 +82,278 @@ impl Drop for Pattern {
 }
 
 impl Pattern {
-    pub fn get_bool(&self, object: &[u8]) -> Option<bool> {
-        self.get_bool_at(object, 0)
+    pub fn unparse(&self) -> Option<String> {
+        let raw_name = unsafe { FcNameUnparse(self.raw) };
+        if raw_name.is_null() {
+            return None;
+        }
+        let name = unsafe { CStr::from_ptr(raw_name as _) }
+            .to_str()
+            .ok()
+            .map(ToOwned::to_owned);
+        unsafe { FcStrFree(raw_name) };
+        name
+    }
+}
+
+impl Pattern {
+    pub fn get_bool(&self, object: &CStr) -> Option<bool> {
+        self.get_bool_at(object, 0).ok()
     }
 
-    pub fn get_bool_at(&self, object: &[u8], index: usize) -> Option<bool> {
+    pub fn get_bool_at(&self, object: &CStr, index: usize) -> Result<bool, Error> {
         let mut value = Default::default();
-        let result =
-            unsafe { FcPatternGetBool(self.raw, object.as_ptr() as _, index as _, &mut value) };
+        let result = unsafe { FcPatternGetBool(self.raw, object.as_ptr(), index as _, &mut value) };
         if result == FcResultMatch {
-            Some(value != FcFalse)
+            Ok(value != FcFalse)
         } else {
-            None
+            Err(result.into())
         }
     }
 
-    pub fn get_i32(&self, object: &[u8]) -> Option<i32> {
-        self.get_i32_at(object, 0)
+    pub fn get_i32(&self, object: &CStr) -> Option<i32> {
+        self.get_i32_at(object, 0).ok()
     }
 
-    pub fn get_i32_at(&self, object: &[u8], index: usize) -> Option<i32> {
+    pub fn get_i32_at(&self, object: &CStr, index: usize) -> Result<i32, Error> {
         let mut value = Default::default();
         let result =
-            unsafe { FcPatternGetInteger(self.raw, object.as_ptr() as _, index as _, &mut value) };
+            unsafe { FcPatternGetInteger(self.raw, object.as_ptr(), index as _, &mut value) };
         if result == FcResultMatch {
-            Some(value as _)
+            Ok(value as _)
         } else {
-            None
+            Err(result.into())
         }
     }
 
-    pub fn get_f64(&self, object: &[u8]) -> Option<f64> {
-        self.get_f64_at(object, 0)
+    pub fn get_f64(&self, object: &CStr) -> Option<f64> {
+        self.get_f64_at(object, 0).ok()
     }
 
-    pub fn get_f64_at(&self, object: &[u8], index: usize) -> Option<f64> {
+    pub fn get_f64_at(&self, object: &CStr, index: usize) -> Result<f64, Error> {
         let mut value = Default::default();
         let result =
-            unsafe { FcPatternGetDouble(self.raw, object.as_ptr() as _, index as _, &mut value) };
+            unsafe { FcPatternGetDouble(self.raw, object.as_ptr(), index as _, &mut value) };
         if result == FcResultMatch {
-            Some(value as _)
+            Ok(value as _)
         } else {
-            None
+            Err(result.into())
         }
     }
 
-    pub fn get_str(&self, object: &[u8]) -> Option<&str> {
-        self.get_str_at(object, 0)
+    pub fn get_str(&self, object: &CStr) -> Option<&str> {
+        self.get_str_at(object, 0).ok()
     }
 
-    pub fn get_str_at(&self, object: &[u8], index: usize) -> Option<&str> {
+    pub fn get_str_at(&self, object: &CStr, index: usize) -> Result<&str, Error> {
         let mut value = ptr::null_mut();
         let result =
-            unsafe { FcPatternGetString(self.raw, object.as_ptr() as _, index as _, &mut value) };
+            unsafe { FcPatternGetString(self.raw, object.as_ptr(), index as _, &mut value) };
         if result == FcResultMatch {
-            unsafe { CStr::from_ptr(value as _) }.to_str().ok()
+            unsafe { CStr::from_ptr(value as _) }
+                .to_str()
+                .map_err(|_| Error::InvalidUtf8)
         } else {
-            None
+            Err(result.into())
         }
     }
 
     pub fn get_freetype_face<'a>(
         &self,
-        object: &[u8],
+        object: &CStr,
         library: &'a freetype::Library,
     ) -> Option<freetype::Face<'a>> {
-        self.get_freetype_face_at(object, 0, library)
+        self.get_freetype_face_at(object, 0, library).ok()
     }
 
     pub fn get_freetype_face_at<'a>(
         &self,
-        object: &[u8],
+        object: &CStr,
         index: usize,
         library: &'a freetype::Library,
-    ) -> Option<freetype::Face<'a>> {
+    ) -> Result<freetype::Face<'a>, Error> {
         let mut value = ptr::null_mut();
         let result =
-            unsafe { FcPatternGetFTFace(self.raw, object.as_ptr() as _, index as _, &mut value) };
+            unsafe { FcPatternGetFTFace(self.raw, object.as_ptr(), index as _, &mut value) };
         if result == FcResultMatch {
-            Some(unsafe { freetype::Face::from_raw(value, library) })
+            Ok(unsafe {
+                freetype::FT_Reference_Face(value);
+                freetype::Face::from_raw(value, library)
+            })
         } else {
-            None
+            Err(result.into())
+        }
+    }
+}
+
+impl Pattern {
+    pub fn add_bool(&mut self, object: &CStr, value: bool) -> bool {
+        let value = if value { FcTrue } else { FcFalse };
+        let result = unsafe { FcPatternAddBool(self.raw, object.as_ptr(), value) };
+        result != FcFalse
+    }
+
+    pub fn add_i32(&mut self, object: &CStr, value: i32) -> bool {
+        let result = unsafe { FcPatternAddInteger(self.raw, object.as_ptr(), value as _) };
+        result != FcFalse
+    }
+
+    pub fn add_f64(&mut self, object: &CStr, value: f64) -> bool {
+        let result = unsafe { FcPatternAddDouble(self.raw, object.as_ptr(), value as _) };
+        result != FcFalse
+    }
+
+    pub fn add_str<V>(&mut self, object: &CStr, value: V) -> bool
+    where
+        V: AsRef<str>,
+    {
+        if let Ok(value) = CString::new(value.as_ref()) {
+            let result =
+                unsafe { FcPatternAddString(self.raw, object.as_ptr(), value.as_ptr() as _) };
+            result != FcFalse
+        } else {
+            false
         }
     }
+
+    pub fn remove(&mut self, object: &CStr) -> bool {
+        let result = unsafe { FcPatternDel(self.raw, object.as_ptr()) };
+        result != FcFalse
+    }
+
+    pub fn default_substitute(&mut self) {
+        unsafe { FcDefaultSubstitute(self.raw) };
+    }
 }
 
 impl Pattern {
     pub fn file(&self) -> Option<&str> {
-        self.get_str(FC_FILE)
+        self.get_str(object_name(FC_FILE))
+    }
+
+    pub fn file_path(&self) -> Option<&Path> {
+        let mut value = ptr::null_mut();
+        let result =
+            unsafe { FcPatternGetString(self.raw, object_name(FC_FILE).as_ptr(), 0, &mut value) };
+        if result == FcResultMatch {
+            let bytes = unsafe { CStr::from_ptr(value as _) }.to_bytes();
+            Some(Path::new(OsStr::from_bytes(bytes)))
+        } else {
+            None
+        }
     }
 
     pub fn index(&self) -> Option<i32> {
-        self.get_i32(FC_INDEX)
+        self.get_i32(object_name(FC_INDEX))
     }
 
     pub fn fullname(&self) -> Option<&str> {
-        self.get_str(FC_FULLNAME)
+        self.get_str(object_name(FC_FULLNAME))
     }
 
     pub fn postscript_name(&self) -> Option<&str> {
-        self.get_str(FC_POSTSCRIPT_NAME)
+        self.get_str(object_name(FC_POSTSCRIPT_NAME))
     }
 
     pub fn is_variable(&self) -> Option<bool> {
-        self.get_bool(FC_VARIABLE)
+        self.get_bool(object_name(FC_VARIABLE))
     }
 
     pub fn family(&self) -> Option<&str> {
-        self.get_str(FC_FAMILY)
+        self.get_str(object_name(FC_FAMILY))
     }
 
     pub fn style(&self) -> Option<&str> {
-        self.get_str(FC_STYLE)
+        self.get_str(object_name(FC_STYLE))
     }
 
     pub fn weight(&self) -> Option<i32> {
-        self.get_i32(FC_WEIGHT)
+        self.get_i32(object_name(FC_WEIGHT))
     }
 
     pub fn slant(&self) -> Option<i32> {
-        self.get_i32(FC_SLANT)
+        self.get_i32(object_name(FC_SLANT))
     }
 
     pub fn width(&self) -> Option<i32> {
-        self.get_i32(FC_WIDTH)
+        self.get_i32(object_name(FC_WIDTH))
+    }
+
+    /// Languages the font covers, as RFC 3066 tags like `"en"` or `"zh-cn"`.
+    pub fn languages(&self) -> Vec<String> {
+        let mut lang_set = ptr::null_mut();
+        let result = unsafe {
+            FcPatternGetLangSet(self.raw, object_name(FC_LANG).as_ptr(), 0, &mut lang_set)
+        };
+        if result != FcResultMatch {
+            return Vec::new();
+        }
+        let raw_str_set = unsafe { FcLangSetGetLangs(lang_set) };
+        if raw_str_set.is_null() {
+            return Vec::new();
+        }
+        let str_set = unsafe { StrSet::from_raw(raw_str_set) };
+        let languages = str_set.iter().flatten().map(ToOwned::to_owned).collect();
+        languages
     }
 
     pub fn freetype_face<'a>(&self, library: &'a freetype::Library) -> Option<freetype::Face<'a>> {
-        self.get_freetype_face(FC_FT_FACE, library)
+        self.get_freetype_face(object_name(FC_FT_FACE), library)
+    }
+}
+
+fn object_name(object: &'static [u8]) -> &'static CStr {
+    // The FC_* object names are generated with their trailing NUL.
+    unsafe { CStr::from_bytes_with_nul_unchecked(object) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern() -> Pattern {
+        let mut pattern = Pattern::new();
+        assert!(pattern.add_str(c"family", "Inter"));
+        assert!(pattern.add_str(c"family", "Inter Display"));
+        assert!(pattern.add_i32(c"weight", 200));
+        assert!(pattern.add_f64(c"size", 12.5));
+        assert!(pattern.add_bool(c"variable", true));
+        pattern
+    }
+
+    #[test]
+    fn typed_getters_read_values() {
+        let pattern = pattern();
+        assert_eq!(pattern.get_str(c"family"), Some("Inter"));
+        assert_eq!(pattern.get_str_at(c"family", 1), Ok("Inter Display"));
+        assert_eq!(pattern.get_i32(c"weight"), Some(200));
+        assert_eq!(pattern.get_f64(c"size"), Some(12.5));
+        assert_eq!(pattern.get_bool(c"variable"), Some(true));
+        assert_eq!(pattern.family(), Some("Inter"));
+        assert_eq!(pattern.weight(), Some(200));
+    }
+
+    #[test]
+    fn typed_getters_report_missing_objects() {
+        let pattern = pattern();
+        assert_eq!(pattern.get_str(c"style"), None);
+        assert_eq!(pattern.get_i32_at(c"slant", 0), Err(Error::NoMatch));
+        assert_eq!(pattern.get_i32_at(c"weight", 1), Err(Error::NoId));
+    }
+
+    #[test]
+    fn typed_getters_report_type_mismatches() {
+        let pattern = pattern();
+        assert_eq!(pattern.get_i32(c"family"), None);
+        assert_eq!(pattern.get_str_at(c"weight", 0), Err(Error::TypeMismatch));
+        assert_eq!(pattern.get_bool_at(c"family", 0), Err(Error::TypeMismatch));
+    }
+
+    #[test]
+    fn remove_deletes_every_value() {
+        let mut pattern = pattern();
+        assert!(pattern.remove(c"family"));
+        assert_eq!(pattern.get_str_at(c"family", 0), Err(Error::NoMatch));
+        assert!(!pattern.remove(c"family"));
     }
 }
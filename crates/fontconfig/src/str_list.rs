@@ -1,8 +1,8 @@
 #![allow(clippy::missing_safety_doc)]
 
-use std::{ffi::CStr, marker::PhantomData};
+use std::{ffi::CStr, marker::PhantomData, str};
 
-use fontconfig_sys::{FcStrList, FcStrListCreate, FcStrListDone, FcStrListNext};
+use fontconfig_sys::{FcStrList, FcStrListCreate, FcStrListDone, FcStrListFirst, FcStrListNext};
 
 use crate::StrSet;
 
@@ -33,21 +33,30 @@ impl<'a> StrList<'a> {
             _marker: PhantomData,
         }
     }
-}
 
-impl<'a> Iterator for StrList<'a> {
-    type Item = Option<&'a str>;
+    pub fn rewind(&mut self) {
+        unsafe { FcStrListFirst(self.raw) };
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Returns the next string without requiring it to be valid UTF-8.
+    pub fn next_bytes(&mut self) -> Option<&'a [u8]> {
         let raw_str = unsafe { FcStrListNext(self.raw) };
         if !raw_str.is_null() {
-            Some(unsafe { CStr::from_ptr(raw_str as _) }.to_str().ok())
+            Some(unsafe { CStr::from_ptr(raw_str as _) }.to_bytes())
         } else {
             None
         }
     }
 }
 
+impl<'a> Iterator for StrList<'a> {
+    type Item = Option<&'a str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_bytes().map(|bytes| str::from_utf8(bytes).ok())
+    }
+}
+
 impl Drop for StrList<'_> {
     fn drop(&mut self) {
         unsafe { FcStrListDone(self.raw) };
@@ -9,3 +9,13 @@
 #include FT_SFNT_NAMES_H
 #include FT_TRUETYPE_IDS_H
 #include FT_MULTIPLE_MASTERS_H
+#include FT_ADVANCES_H
+#include FT_BBOX_H
+#include FT_TRUETYPE_TABLES_H
+#include FT_OUTLINE_H
+#include FT_LCD_FILTER_H
+#include FT_COLOR_H
+
+/* FreeType 2.13.3 changed the outline contour end points from short to
+   unsigned short. */
+typedef __typeof__(*((FT_Outline *)0)->contours) FT_Outline_Contour;
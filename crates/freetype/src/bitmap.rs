@@ -0,0 +1,38 @@
+use std::slice;
+
+use freetype_sys::FT_Bitmap;
+
+pub struct Bitmap<'a> {
+    raw: &'a FT_Bitmap,
+}
+
+impl<'a> Bitmap<'a> {
+    pub fn new(raw: &'a FT_Bitmap) -> Bitmap<'a> {
+        Bitmap { raw }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.raw.width as _
+    }
+
+    pub fn rows(&self) -> u32 {
+        self.raw.rows as _
+    }
+
+    pub fn pitch(&self) -> i32 {
+        self.raw.pitch as _
+    }
+
+    pub fn pixel_mode(&self) -> u8 {
+        self.raw.pixel_mode as _
+    }
+
+    pub fn buffer(&self) -> &'a [u8] {
+        if !self.raw.buffer.is_null() {
+            let len = self.raw.rows as usize * self.raw.pitch.unsigned_abs() as usize;
+            unsafe { slice::from_raw_parts(self.raw.buffer, len) }
+        } else {
+            &[]
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use freetype_sys::{FT_Get_First_Char, FT_Get_Next_Char};
+
+use crate::Face;
+
+pub struct CharCodeIter<'a> {
+    face: &'a Face<'a>,
+    char_code: Option<u32>,
+}
+
+impl<'a> CharCodeIter<'a> {
+    pub fn new(face: &'a Face) -> CharCodeIter<'a> {
+        CharCodeIter {
+            face,
+            char_code: None,
+        }
+    }
+}
+
+impl Iterator for CharCodeIter<'_> {
+    type Item = (u32, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut glyph_index = 0;
+        let char_code = match self.char_code {
+            Some(char_code) => unsafe {
+                FT_Get_Next_Char(self.face.raw(), char_code as _, &mut glyph_index)
+            },
+            None => unsafe { FT_Get_First_Char(self.face.raw(), &mut glyph_index) },
+        };
+        if glyph_index != 0 {
+            self.char_code = Some(char_code as _);
+            Some((char_code as _, glyph_index))
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use freetype_sys::{FT_CharMapRec, FT_Encoding};
+
+pub struct CharMap<'a> {
+    raw: &'a FT_CharMapRec,
+}
+
+impl<'a> CharMap<'a> {
+    pub fn new(raw: &'a FT_CharMapRec) -> CharMap<'a> {
+        CharMap { raw }
+    }
+
+    pub fn platform_id(&self) -> u16 {
+        self.raw.platform_id
+    }
+
+    pub fn encoding_id(&self) -> u16 {
+        self.raw.encoding_id
+    }
+
+    pub fn encoding(&self) -> FT_Encoding {
+        self.raw.encoding
+    }
+}
@@ -0,0 +1,53 @@
+#![allow(clippy::missing_safety_doc)]
+
+use std::{marker::PhantomData, ptr};
+
+use freetype_sys::{FT_Face, FT_Get_Color_Glyph_Layer, FT_LayerIterator};
+
+use crate::GlyphSlot;
+
+/// Yields `(glyph_index, color_index)` for each COLR v0 layer of a glyph,
+/// from bottom to top. A color index of `0xFFFF` means the text color.
+pub struct ColorLayerIter<'a> {
+    face: FT_Face,
+    base_glyph: u32,
+    iterator: FT_LayerIterator,
+    _marker: PhantomData<&'a GlyphSlot<'a>>,
+}
+
+impl<'a> ColorLayerIter<'a> {
+    pub unsafe fn new(face: FT_Face, base_glyph: u32) -> ColorLayerIter<'a> {
+        ColorLayerIter {
+            face,
+            base_glyph,
+            iterator: FT_LayerIterator {
+                num_layers: 0,
+                layer: 0,
+                p: ptr::null_mut(),
+            },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Iterator for ColorLayerIter<'_> {
+    type Item = (u32, u16);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (mut glyph_index, mut color_index) = (0, 0);
+        let result = unsafe {
+            FT_Get_Color_Glyph_Layer(
+                self.face,
+                self.base_glyph,
+                &mut glyph_index,
+                &mut color_index,
+                &mut self.iterator,
+            )
+        };
+        if result != 0 {
+            Some((glyph_index, color_index as _))
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::{error, fmt};
+
+use freetype_sys::{
+    FT_Err_Cannot_Open_Resource, FT_Err_Cannot_Render_Glyph, FT_Err_Invalid_Argument,
+    FT_Err_Invalid_Character_Code, FT_Err_Invalid_File_Format, FT_Err_Invalid_Glyph_Format,
+    FT_Err_Invalid_Glyph_Index, FT_Err_Invalid_Handle, FT_Err_Invalid_Pixel_Size,
+    FT_Err_Invalid_Table, FT_Err_Invalid_Version, FT_Err_Out_Of_Memory, FT_Err_Table_Missing,
+    FT_Err_Unimplemented_Feature, FT_Err_Unknown_File_Format, FT_Error,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    CannotOpenResource,
+    UnknownFileFormat,
+    InvalidFileFormat,
+    InvalidVersion,
+    InvalidArgument,
+    UnimplementedFeature,
+    InvalidTable,
+    InvalidGlyphIndex,
+    InvalidCharacterCode,
+    InvalidGlyphFormat,
+    CannotRenderGlyph,
+    InvalidPixelSize,
+    InvalidHandle,
+    OutOfMemory,
+    TableMissing,
+    Other(FT_Error),
+}
+
+impl Error {
+    pub fn raw_code(&self) -> FT_Error {
+        match *self {
+            Error::CannotOpenResource => FT_Err_Cannot_Open_Resource,
+            Error::UnknownFileFormat => FT_Err_Unknown_File_Format,
+            Error::InvalidFileFormat => FT_Err_Invalid_File_Format,
+            Error::InvalidVersion => FT_Err_Invalid_Version,
+            Error::InvalidArgument => FT_Err_Invalid_Argument,
+            Error::UnimplementedFeature => FT_Err_Unimplemented_Feature,
+            Error::InvalidTable => FT_Err_Invalid_Table,
+            Error::InvalidGlyphIndex => FT_Err_Invalid_Glyph_Index,
+            Error::InvalidCharacterCode => FT_Err_Invalid_Character_Code,
+            Error::InvalidGlyphFormat => FT_Err_Invalid_Glyph_Format,
+            Error::CannotRenderGlyph => FT_Err_Cannot_Render_Glyph,
+            Error::InvalidPixelSize => FT_Err_Invalid_Pixel_Size,
+            Error::InvalidHandle => FT_Err_Invalid_Handle,
+            Error::OutOfMemory => FT_Err_Out_Of_Memory,
+            Error::TableMissing => FT_Err_Table_Missing,
+            Error::Other(code) => code,
+        }
+    }
+}
+
+impl From<FT_Error> for Error {
+    #[allow(non_upper_case_globals)]
+    fn from(code: FT_Error) -> Self {
+        match code {
+            FT_Err_Cannot_Open_Resource => Error::CannotOpenResource,
+            FT_Err_Unknown_File_Format => Error::UnknownFileFormat,
+            FT_Err_Invalid_File_Format => Error::InvalidFileFormat,
+            FT_Err_Invalid_Version => Error::InvalidVersion,
+            FT_Err_Invalid_Argument => Error::InvalidArgument,
+            FT_Err_Unimplemented_Feature => Error::UnimplementedFeature,
+            FT_Err_Invalid_Table => Error::InvalidTable,
+            FT_Err_Invalid_Glyph_Index => Error::InvalidGlyphIndex,
+            FT_Err_Invalid_Character_Code => Error::InvalidCharacterCode,
+            FT_Err_Invalid_Glyph_Format => Error::InvalidGlyphFormat,
+            FT_Err_Cannot_Render_Glyph => Error::CannotRenderGlyph,
+            FT_Err_Invalid_Pixel_Size => Error::InvalidPixelSize,
+            FT_Err_Invalid_Handle => Error::InvalidHandle,
+            FT_Err_Out_Of_Memory => Error::OutOfMemory,
+            FT_Err_Table_Missing => Error::TableMissing,
+            code => Error::Other(code),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::CannotOpenResource => f.write_str("cannot open resource"),
+            Error::UnknownFileFormat => f.write_str("unknown file format"),
+            Error::InvalidFileFormat => f.write_str("broken file"),
+            Error::InvalidVersion => f.write_str("invalid FreeType version"),
+            Error::InvalidArgument => f.write_str("invalid argument"),
+            Error::UnimplementedFeature => f.write_str("unimplemented feature"),
+            Error::InvalidTable => f.write_str("broken table"),
+            Error::InvalidGlyphIndex => f.write_str("invalid glyph index"),
+            Error::InvalidCharacterCode => f.write_str("invalid character code"),
+            Error::InvalidGlyphFormat => f.write_str("unsupported glyph image format"),
+            Error::CannotRenderGlyph => f.write_str("cannot render this glyph format"),
+            Error::InvalidPixelSize => f.write_str("invalid pixel size"),
+            Error::InvalidHandle => f.write_str("invalid object handle"),
+            Error::OutOfMemory => f.write_str("out of memory"),
+            Error::TableMissing => f.write_str("table missing"),
+            Error::Other(code) => write!(f, "FreeType error {:#04x}", code),
+        }
+    }
+}
+
+impl error::Error for Error {}
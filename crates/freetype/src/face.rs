@@ -2,19 +2,35 @@
 
 use std::{
     ffi::{CStr, CString},
-    ptr,
+    fmt, ptr, slice,
+    sync::Arc,
 };
 
 use freetype_sys::{
-    FT_Done_Face, FT_Err_Ok, FT_Face, FT_Get_Postscript_Name, FT_Get_Sfnt_Name,
-    FT_Get_Sfnt_Name_Count, FT_New_Face,
+    FT_Bitmap_Size, FT_Done_Face, FT_Done_MM_Var, FT_Encoding, FT_Encoding__FT_ENCODING_UNICODE,
+    FT_Err_Ok, FT_Face, FT_Face_GetCharVariantIndex, FT_Fixed, FT_Get_Advance, FT_Get_Advances,
+    FT_Get_Char_Index, FT_Get_Glyph_Name, FT_Get_Kerning, FT_Get_MM_Var, FT_Get_Postscript_Name,
+    FT_Get_Sfnt_Name, FT_Get_Sfnt_Name_Count, FT_Get_Track_Kerning, FT_Get_Var_Blend_Coordinates,
+    FT_Get_Var_Design_Coordinates, FT_Load_Glyph, FT_Load_Sfnt_Table, FT_New_Face,
+    FT_New_Memory_Face, FT_Palette_Data, FT_Palette_Data_Get, FT_Select_Charmap, FT_Select_Size,
+    FT_Set_Char_Size, FT_Set_Pixel_Sizes, FT_Set_Var_Design_Coordinates, FT_Vector,
+    TT_MS_LANGID_CHINESE_HONG_KONG, TT_MS_LANGID_CHINESE_PRC, TT_MS_LANGID_CHINESE_TAIWAN,
+    TT_MS_LANGID_ENGLISH_UNITED_KINGDOM, TT_MS_LANGID_ENGLISH_UNITED_STATES,
+    TT_MS_LANGID_FRENCH_FRANCE, TT_MS_LANGID_GERMAN_GERMANY, TT_MS_LANGID_ITALIAN_ITALY,
+    TT_MS_LANGID_JAPANESE_JAPAN, TT_MS_LANGID_KOREAN_KOREA, TT_MS_LANGID_PORTUGUESE_BRAZIL,
+    TT_MS_LANGID_RUSSIAN_RUSSIA, TT_PLATFORM_MICROSOFT,
 };
 
-use crate::{Library, MMVar, SfntName};
+use crate::{
+    CharCodeIter, CharMap, Error, FaceFlags, GlyphSlot, Library, MMVar, SfntName, SizeMetrics,
+    StyleFlags,
+};
 
 pub struct Face<'a> {
     raw: FT_Face,
     library: &'a Library,
+    // Keeps the buffer of a shared memory face alive.
+    _data: Option<Arc<Vec<u8>>>,
 }
 
 impl Face<'_> {
@@ -24,34 +40,97 @@ impl Face<'_> {
 }
 
 impl Face<'_> {
-    pub fn from_file<P>(library: &Library, path: P, face_index: isize) -> Option<Face>
+    pub fn from_file<P>(library: &Library, path: P, face_index: isize) -> Result<Face, Error>
     where
         P: AsRef<str>,
     {
         let mut raw = ptr::null_mut();
-        let path = CString::new(path.as_ref()).ok()?;
+        let path = CString::new(path.as_ref()).map_err(|_| Error::InvalidArgument)?;
         let result =
             unsafe { FT_New_Face(library.raw(), path.as_ptr(), face_index as _, &mut raw) };
         if result == FT_Err_Ok {
-            Some(Face { raw, library })
+            Ok(Face {
+                raw,
+                library,
+                _data: None,
+            })
         } else {
-            None
+            Err(result.into())
+        }
+    }
+
+    pub fn from_memory<'a>(
+        library: &'a Library,
+        data: &'a [u8],
+        face_index: isize,
+    ) -> Result<Face<'a>, Error> {
+        let mut raw = ptr::null_mut();
+        let result = unsafe {
+            FT_New_Memory_Face(
+                library.raw(),
+                data.as_ptr(),
+                data.len() as _,
+                face_index as _,
+                &mut raw,
+            )
+        };
+        if result == FT_Err_Ok {
+            Ok(Face {
+                raw,
+                library,
+                _data: None,
+            })
+        } else {
+            Err(result.into())
+        }
+    }
+
+    pub fn from_shared_memory(
+        library: &Library,
+        data: Arc<Vec<u8>>,
+        face_index: isize,
+    ) -> Result<Face, Error> {
+        let mut raw = ptr::null_mut();
+        let result = unsafe {
+            FT_New_Memory_Face(
+                library.raw(),
+                data.as_ptr(),
+                data.len() as _,
+                face_index as _,
+                &mut raw,
+            )
+        };
+        if result == FT_Err_Ok {
+            Ok(Face {
+                raw,
+                library,
+                _data: Some(data),
+            })
+        } else {
+            Err(result.into())
         }
     }
 
     pub unsafe fn from_raw(raw: FT_Face, library: &Library) -> Face {
-        Face { raw, library }
+        Face {
+            raw,
+            library,
+            _data: None,
+        }
     }
 }
 
 impl Drop for Face<'_> {
     fn drop(&mut self) {
-        let result = unsafe { FT_Done_Face(self.raw) };
-        assert!(result == FT_Err_Ok);
+        unsafe { FT_Done_Face(self.raw) };
     }
 }
 
 impl Face<'_> {
+    pub fn library(&self) -> &Library {
+        self.library
+    }
+
     pub fn postscript_name(&self) -> Option<&str> {
         let raw_postscript_name = unsafe { FT_Get_Postscript_Name(self.raw) };
         if !raw_postscript_name.is_null() {
@@ -79,6 +158,67 @@ impl Face<'_> {
         }
     }
 
+    pub fn face_flags(&self) -> FaceFlags {
+        FaceFlags::from_bits_retain(unsafe { (*self.raw).face_flags } as _)
+    }
+
+    pub fn style_flags(&self) -> StyleFlags {
+        StyleFlags::from_bits_retain(unsafe { (*self.raw).style_flags } as u32 & 0xFFFF)
+    }
+
+    pub fn num_faces(&self) -> usize {
+        unsafe { (*self.raw).num_faces as _ }
+    }
+
+    pub fn num_glyphs(&self) -> usize {
+        unsafe { (*self.raw).num_glyphs as _ }
+    }
+
+    pub fn num_fixed_sizes(&self) -> usize {
+        unsafe { (*self.raw).num_fixed_sizes as _ }
+    }
+
+    pub fn available_sizes(&self) -> &[FT_Bitmap_Size] {
+        unsafe {
+            if (*self.raw).num_fixed_sizes > 0 && !(*self.raw).available_sizes.is_null() {
+                slice::from_raw_parts((*self.raw).available_sizes, self.num_fixed_sizes())
+            } else {
+                &[]
+            }
+        }
+    }
+
+    pub fn units_per_em(&self) -> u16 {
+        unsafe { (*self.raw).units_per_EM }
+    }
+
+    pub fn ascender(&self) -> i16 {
+        unsafe { (*self.raw).ascender }
+    }
+
+    pub fn descender(&self) -> i16 {
+        unsafe { (*self.raw).descender }
+    }
+
+    pub fn height(&self) -> i16 {
+        unsafe { (*self.raw).height }
+    }
+
+    /// Returns `None` until a size has been set with `set_char_size` or
+    /// `set_pixel_sizes`.
+    pub fn metrics(&self) -> Option<SizeMetrics> {
+        let size = unsafe { (*self.raw).size };
+        if size.is_null() {
+            return None;
+        }
+        let metrics = unsafe { (*size).metrics };
+        if metrics.x_ppem != 0 || metrics.y_ppem != 0 {
+            Some(SizeMetrics::new(metrics))
+        } else {
+            None
+        }
+    }
+
     pub fn find_sfnt_name<P>(&self, mut predicate: P) -> Option<SfntName>
     where
         P: FnMut(&SfntName) -> bool,
@@ -94,7 +234,419 @@ impl Face<'_> {
         None
     }
 
-    pub fn mm_var(&self) -> Option<MMVar> {
-        MMVar::from_face(self, self.library)
+    /// Looks up name record `name_id`, preferring the Windows record for
+    /// `lang` (e.g. `"ja-JP"`), then Windows en-US, then any record that
+    /// decodes.
+    pub fn sfnt_name_for(&self, name_id: u16, lang: Option<&str>) -> Option<String> {
+        let language_id = lang.and_then(ms_language_id);
+        let mut sfnt_name = unsafe { SfntName::new() };
+        let mut best: Option<(u8, String)> = None;
+        let count = unsafe { FT_Get_Sfnt_Name_Count(self.raw) };
+        for index in 0..count {
+            let result = unsafe { FT_Get_Sfnt_Name(self.raw, index, sfnt_name.as_mut()) };
+            if result != FT_Err_Ok || sfnt_name.name_id() != name_id {
+                continue;
+            }
+            let Some(name) = sfnt_name.decoded() else {
+                continue;
+            };
+            let is_microsoft = sfnt_name.platform_id() == TT_PLATFORM_MICROSOFT as u16;
+            let rank = if is_microsoft && Some(sfnt_name.language_id()) == language_id {
+                0
+            } else if is_microsoft
+                && sfnt_name.language_id() == TT_MS_LANGID_ENGLISH_UNITED_STATES as u16
+            {
+                1
+            } else {
+                2
+            };
+            if rank == 0 {
+                return Some(name);
+            }
+            if best.as_ref().is_none_or(|&(best_rank, _)| rank < best_rank) {
+                best = Some((rank, name));
+            }
+        }
+        best.map(|(_, name)| name)
+    }
+
+    /// Loads the raw SFNT table with the given big-endian `tag`, e.g.
+    /// `u32::from_be_bytes(*b"GSUB")`.
+    pub fn sfnt_table(&self, tag: u32) -> Result<Vec<u8>, Error> {
+        let mut length = 0;
+        let result =
+            unsafe { FT_Load_Sfnt_Table(self.raw, tag as _, 0, ptr::null_mut(), &mut length) };
+        if result != FT_Err_Ok {
+            return Err(result.into());
+        }
+        let mut buffer = vec![0; length as usize];
+        let result =
+            unsafe { FT_Load_Sfnt_Table(self.raw, tag as _, 0, buffer.as_mut_ptr(), &mut length) };
+        if result == FT_Err_Ok {
+            Ok(buffer)
+        } else {
+            Err(result.into())
+        }
+    }
+
+    pub fn is_variable(&self) -> bool {
+        self.face_flags().contains(FaceFlags::MULTIPLE_MASTERS)
+    }
+
+    pub fn is_color(&self) -> bool {
+        self.face_flags().contains(FaceFlags::COLOR)
+    }
+
+    /// Number of CPAL palettes, or 0 if the face has none.
+    pub fn palette_count(&self) -> usize {
+        let mut palette_data = FT_Palette_Data {
+            num_palettes: 0,
+            palette_name_ids: ptr::null(),
+            palette_flags: ptr::null(),
+            num_palette_entries: 0,
+            palette_entry_name_ids: ptr::null(),
+        };
+        let result = unsafe { FT_Palette_Data_Get(self.raw, &mut palette_data) };
+        if result == FT_Err_Ok {
+            palette_data.num_palettes as _
+        } else {
+            0
+        }
+    }
+
+    pub fn mm_var(&self) -> Result<MMVar, Error> {
+        MMVar::from_face(self)
+    }
+
+    /// Current design coordinates in 16.16 fixed-point, one per axis.
+    pub fn var_design_coords(&self) -> Result<Vec<FT_Fixed>, Error> {
+        let mut coords = vec![0; self.var_axis_count()?];
+        let result = unsafe {
+            FT_Get_Var_Design_Coordinates(self.raw, coords.len() as _, coords.as_mut_ptr())
+        };
+        if result == FT_Err_Ok {
+            Ok(coords)
+        } else {
+            Err(result.into())
+        }
+    }
+
+    /// Fails with `InvalidArgument` unless there is one coordinate per axis.
+    pub fn set_var_design_coords(&self, coords: &[FT_Fixed]) -> Result<(), Error> {
+        if coords.len() != self.var_axis_count()? {
+            return Err(Error::InvalidArgument);
+        }
+        let mut coords = coords.to_vec();
+        let result = unsafe {
+            FT_Set_Var_Design_Coordinates(self.raw, coords.len() as _, coords.as_mut_ptr())
+        };
+        if result == FT_Err_Ok {
+            Ok(())
+        } else {
+            Err(result.into())
+        }
+    }
+
+    /// Current normalized blend coordinates in 16.16 fixed-point, one per
+    /// axis, each between -1.0 and 1.0.
+    pub fn get_var_blend_coordinates(&self) -> Result<Vec<FT_Fixed>, Error> {
+        let mut coords = vec![0; self.var_axis_count()?];
+        let result = unsafe {
+            FT_Get_Var_Blend_Coordinates(self.raw, coords.len() as _, coords.as_mut_ptr())
+        };
+        if result == FT_Err_Ok {
+            Ok(coords)
+        } else {
+            Err(result.into())
+        }
+    }
+
+    fn var_axis_count(&self) -> Result<usize, Error> {
+        let mut raw = ptr::null_mut();
+        let result = unsafe { FT_Get_MM_Var(self.raw, &mut raw) };
+        if result != FT_Err_Ok {
+            return Err(result.into());
+        }
+        let axis_count = unsafe { (*raw).num_axis as _ };
+        unsafe { FT_Done_MM_Var(self.library.raw(), raw) };
+        Ok(axis_count)
+    }
+
+    /// Returns the glyph index for `charcode` in the active charmap, or 0 if
+    /// the face does not cover it.
+    pub fn char_index(&self, charcode: u32) -> u32 {
+        unsafe { FT_Get_Char_Index(self.raw, charcode as _) }
+    }
+
+    /// Glyph index of `charcode` under the Unicode variation selector
+    /// `variant_selector`, or 0 (the undefined glyph) if the face has no such
+    /// variant.
+    pub fn char_variant_index(&self, charcode: u32, variant_selector: u32) -> u32 {
+        unsafe { FT_Face_GetCharVariantIndex(self.raw, charcode as _, variant_selector as _) }
+    }
+
+    /// Whether every character of `text` maps to a glyph. Only meaningful
+    /// when the active charmap is Unicode; see [`Face::select_charmap`].
+    pub fn covers(&self, text: &str) -> bool {
+        text.chars().all(|char| self.char_index(char as u32) != 0)
+    }
+
+    pub fn char_codes(&self) -> CharCodeIter {
+        CharCodeIter::new(self)
+    }
+
+    pub fn glyph_name(&self, glyph_index: u32) -> Option<String> {
+        if !self.face_flags().contains(FaceFlags::GLYPH_NAMES) {
+            return None;
+        }
+        let mut buffer = [0u8; 256];
+        let result = unsafe {
+            FT_Get_Glyph_Name(
+                self.raw,
+                glyph_index,
+                buffer.as_mut_ptr() as _,
+                buffer.len() as _,
+            )
+        };
+        if result == FT_Err_Ok {
+            let name = CStr::from_bytes_until_nul(&buffer).ok()?;
+            name.to_str().ok().map(ToOwned::to_owned)
+        } else {
+            None
+        }
+    }
+
+    pub fn kerning(
+        &self,
+        left_glyph: u32,
+        right_glyph: u32,
+        kern_mode: u32,
+    ) -> Result<FT_Vector, Error> {
+        if !self.face_flags().contains(FaceFlags::KERNING) {
+            return Err(Error::TableMissing);
+        }
+        let mut kerning = FT_Vector { x: 0, y: 0 };
+        let result =
+            unsafe { FT_Get_Kerning(self.raw, left_glyph, right_glyph, kern_mode, &mut kerning) };
+        if result == FT_Err_Ok {
+            Ok(kerning)
+        } else {
+            Err(result.into())
+        }
+    }
+
+    /// Track kerning in 16.16 fixed-point for a 16.16 `point_size` and a
+    /// tightness `degree`, usually between -3 and 3.
+    pub fn track_kerning(&self, point_size: FT_Fixed, degree: i32) -> Result<FT_Fixed, Error> {
+        let mut kerning = 0;
+        let result = unsafe { FT_Get_Track_Kerning(self.raw, point_size, degree, &mut kerning) };
+        if result == FT_Err_Ok {
+            Ok(kerning)
+        } else {
+            Err(result.into())
+        }
+    }
+
+    /// Advance of `glyph_index` in 16.16 fixed-point, or in font units when
+    /// `load_flags` contains `FT_LOAD_NO_SCALE`.
+    pub fn advance(&self, glyph_index: u32, load_flags: i32) -> Result<FT_Fixed, Error> {
+        let mut advance = 0;
+        let result = unsafe { FT_Get_Advance(self.raw, glyph_index, load_flags, &mut advance) };
+        if result == FT_Err_Ok {
+            Ok(advance)
+        } else {
+            Err(result.into())
+        }
+    }
+
+    /// Advances of `count` glyphs starting at `start`, in the same units as
+    /// [`Face::advance`].
+    pub fn advances(
+        &self,
+        start: u32,
+        count: u32,
+        load_flags: i32,
+    ) -> Result<Vec<FT_Fixed>, Error> {
+        let mut advances = vec![0; count as usize];
+        let result =
+            unsafe { FT_Get_Advances(self.raw, start, count, load_flags, advances.as_mut_ptr()) };
+        if result == FT_Err_Ok {
+            Ok(advances)
+        } else {
+            Err(result.into())
+        }
+    }
+
+    pub fn charmaps(&self) -> impl Iterator<Item = CharMap> {
+        let slice = unsafe {
+            if (*self.raw).num_charmaps > 0 {
+                slice::from_raw_parts((*self.raw).charmaps, (*self.raw).num_charmaps as _)
+            } else {
+                &[]
+            }
+        };
+        slice
+            .iter()
+            .map(|&raw_char_map| CharMap::new(unsafe { &*raw_char_map }))
+    }
+
+    pub fn has_unicode_cmap(&self) -> bool {
+        self.charmaps()
+            .any(|charmap| charmap.encoding() == FT_Encoding__FT_ENCODING_UNICODE)
+    }
+
+    pub fn select_charmap(&self, encoding: FT_Encoding) -> Result<(), Error> {
+        let result = unsafe { FT_Select_Charmap(self.raw, encoding) };
+        if result == FT_Err_Ok {
+            Ok(())
+        } else {
+            Err(result.into())
+        }
+    }
+
+    pub fn set_char_size(
+        &self,
+        char_width: i32,
+        char_height: i32,
+        horz_resolution: u32,
+        vert_resolution: u32,
+    ) -> Result<(), Error> {
+        let result = unsafe {
+            FT_Set_Char_Size(
+                self.raw,
+                char_width as _,
+                char_height as _,
+                horz_resolution,
+                vert_resolution,
+            )
+        };
+        if result == FT_Err_Ok {
+            Ok(())
+        } else {
+            Err(result.into())
+        }
+    }
+
+    pub fn set_pixel_sizes(&self, pixel_width: u32, pixel_height: u32) -> Result<(), Error> {
+        let result = unsafe { FT_Set_Pixel_Sizes(self.raw, pixel_width, pixel_height) };
+        if result == FT_Err_Ok {
+            Ok(())
+        } else {
+            Err(result.into())
+        }
+    }
+
+    /// Selects the bitmap strike at `strike_index` in `available_sizes`.
+    pub fn select_size(&self, strike_index: i32) -> Result<(), Error> {
+        let result = unsafe { FT_Select_Size(self.raw, strike_index) };
+        if result == FT_Err_Ok {
+            Ok(())
+        } else {
+            Err(result.into())
+        }
+    }
+
+    pub fn load_glyph(&mut self, glyph_index: u32, load_flags: i32) -> Result<GlyphSlot, Error> {
+        let result = unsafe { FT_Load_Glyph(self.raw, glyph_index, load_flags) };
+        if result == FT_Err_Ok {
+            Ok(unsafe { GlyphSlot::from_raw((*self.raw).glyph) })
+        } else {
+            Err(result.into())
+        }
+    }
+}
+
+fn ms_language_id(lang: &str) -> Option<u16> {
+    let language_id = match lang {
+        "en" | "en-US" => TT_MS_LANGID_ENGLISH_UNITED_STATES,
+        "en-GB" => TT_MS_LANGID_ENGLISH_UNITED_KINGDOM,
+        "de" | "de-DE" => TT_MS_LANGID_GERMAN_GERMANY,
+        "fr" | "fr-FR" => TT_MS_LANGID_FRENCH_FRANCE,
+        "it" | "it-IT" => TT_MS_LANGID_ITALIAN_ITALY,
+        "ja" | "ja-JP" => TT_MS_LANGID_JAPANESE_JAPAN,
+        "ko" | "ko-KR" => TT_MS_LANGID_KOREAN_KOREA,
+        "pt-BR" => TT_MS_LANGID_PORTUGUESE_BRAZIL,
+        "ru" | "ru-RU" => TT_MS_LANGID_RUSSIAN_RUSSIA,
+        "zh" | "zh-CN" | "zh-Hans" => TT_MS_LANGID_CHINESE_PRC,
+        "zh-HK" => TT_MS_LANGID_CHINESE_HONG_KONG,
+        "zh-TW" | "zh-Hant" => TT_MS_LANGID_CHINESE_TAIWAN,
+        _ => return None,
+    };
+    Some(language_id as _)
+}
+
+impl fmt::Debug for Face<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Face")
+            .field("raw", &self.raw)
+            .field("family_name", &self.family_name())
+            .field("style_name", &self.style_name())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::Error;
+
+    const REGULAR: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../tests/fonts/FixtureSans-Regular.ttf"
+    ));
+
+    #[test]
+    fn reads_em_size_and_vertical_metrics() {
+        let library = crate::init().unwrap();
+        let face = library.face_from_memory(REGULAR, 0).unwrap();
+        assert_eq!(face.units_per_em(), 1000);
+        assert_eq!(face.num_glyphs(), 3);
+        assert_eq!(face.ascender(), 800);
+        assert_eq!(face.descender(), -200);
+        assert_eq!(face.height(), 1000);
+    }
+
+    #[test]
+    fn reads_unscaled_advances() {
+        let library = crate::init().unwrap();
+        let face = library.face_from_memory(REGULAR, 0).unwrap();
+        let load_flags = freetype_sys::FT_LOAD_NO_SCALE as i32;
+        assert_eq!(face.advances(0, 3, load_flags).unwrap(), [500, 250, 600]);
+        assert_eq!(face.advance(2, load_flags).unwrap(), 600);
+    }
+
+    #[test]
+    fn shared_memory_face_keeps_its_buffer_alive() {
+        let library = crate::init().unwrap();
+        let data = Arc::new(REGULAR.to_vec());
+        let weak = Arc::downgrade(&data);
+        let face = library
+            .face_from_shared_memory(Arc::clone(&data), 0)
+            .unwrap();
+        drop(data);
+
+        assert!(weak.upgrade().is_some());
+        assert_eq!(face.family_name(), Some("Fixture Sans"));
+        assert_eq!(face.postscript_name(), Some("FixtureSans-Regular"));
+        drop(face);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn missing_sfnt_names_are_none() {
+        let library = crate::init().unwrap();
+        let face = library.face_from_memory(REGULAR, 0).unwrap();
+        assert_eq!(face.sfnt_name_for(1, None), Some("Fixture Sans".to_owned()));
+        assert_eq!(face.sfnt_name_for(16, None), None);
+        assert_eq!(face.sfnt_name_for(16, Some("en-US")), None);
+        assert!(face.find_sfnt_name(|name| name.name_id() == 16).is_none());
+    }
+
+    #[test]
+    fn paths_with_interior_nul_are_invalid_arguments() {
+        let library = crate::init().unwrap();
+        let result = library.face_from_file("/tmp/fonts\0/Inter.ttf", 0);
+        assert!(matches!(result, Err(Error::InvalidArgument)));
     }
 }
@@ -0,0 +1,30 @@
+use bitflags::bitflags;
+use freetype_sys::{
+    FT_FACE_FLAG_CID_KEYED, FT_FACE_FLAG_COLOR, FT_FACE_FLAG_EXTERNAL_STREAM,
+    FT_FACE_FLAG_FAST_GLYPHS, FT_FACE_FLAG_FIXED_SIZES, FT_FACE_FLAG_FIXED_WIDTH,
+    FT_FACE_FLAG_GLYPH_NAMES, FT_FACE_FLAG_HINTER, FT_FACE_FLAG_HORIZONTAL, FT_FACE_FLAG_KERNING,
+    FT_FACE_FLAG_MULTIPLE_MASTERS, FT_FACE_FLAG_SCALABLE, FT_FACE_FLAG_SFNT, FT_FACE_FLAG_TRICKY,
+    FT_FACE_FLAG_VARIATION, FT_FACE_FLAG_VERTICAL,
+};
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct FaceFlags: u32 {
+        const SCALABLE = FT_FACE_FLAG_SCALABLE;
+        const FIXED_SIZES = FT_FACE_FLAG_FIXED_SIZES;
+        const FIXED_WIDTH = FT_FACE_FLAG_FIXED_WIDTH;
+        const SFNT = FT_FACE_FLAG_SFNT;
+        const HORIZONTAL = FT_FACE_FLAG_HORIZONTAL;
+        const VERTICAL = FT_FACE_FLAG_VERTICAL;
+        const KERNING = FT_FACE_FLAG_KERNING;
+        const FAST_GLYPHS = FT_FACE_FLAG_FAST_GLYPHS;
+        const MULTIPLE_MASTERS = FT_FACE_FLAG_MULTIPLE_MASTERS;
+        const GLYPH_NAMES = FT_FACE_FLAG_GLYPH_NAMES;
+        const EXTERNAL_STREAM = FT_FACE_FLAG_EXTERNAL_STREAM;
+        const HINTER = FT_FACE_FLAG_HINTER;
+        const CID_KEYED = FT_FACE_FLAG_CID_KEYED;
+        const TRICKY = FT_FACE_FLAG_TRICKY;
+        const COLOR = FT_FACE_FLAG_COLOR;
+        const VARIATION = FT_FACE_FLAG_VARIATION;
+    }
+}
@@ -0,0 +1,79 @@
+#![allow(clippy::missing_safety_doc)]
+
+use std::marker::PhantomData;
+
+use freetype_sys::{
+    FT_BBox, FT_Err_Ok, FT_GlyphSlot, FT_Glyph_Format__FT_GLYPH_FORMAT_OUTLINE,
+    FT_Outline_Get_BBox, FT_Render_Glyph, FT_Render_Mode,
+};
+
+use crate::{Bitmap, ColorLayerIter, Error, Face, Outline};
+
+pub struct GlyphSlot<'a> {
+    raw: FT_GlyphSlot,
+    _marker: PhantomData<&'a Face<'a>>,
+}
+
+impl GlyphSlot<'_> {
+    pub unsafe fn raw(&self) -> FT_GlyphSlot {
+        self.raw
+    }
+}
+
+impl<'a> GlyphSlot<'a> {
+    pub unsafe fn from_raw(raw: FT_GlyphSlot) -> GlyphSlot<'a> {
+        GlyphSlot {
+            raw,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn render(&mut self, render_mode: FT_Render_Mode) -> Result<(), Error> {
+        let result = unsafe { FT_Render_Glyph(self.raw, render_mode) };
+        if result == FT_Err_Ok {
+            Ok(())
+        } else {
+            Err(result.into())
+        }
+    }
+
+    /// Exact bounding box of the loaded outline, in 26.6 pixels or in font
+    /// units with `FT_LOAD_NO_SCALE`. Fails with `InvalidGlyphFormat` if the
+    /// slot does not hold an outline.
+    pub fn bbox(&self) -> Result<FT_BBox, Error> {
+        if unsafe { (*self.raw).format } != FT_Glyph_Format__FT_GLYPH_FORMAT_OUTLINE {
+            return Err(Error::InvalidGlyphFormat);
+        }
+        let mut bbox = FT_BBox {
+            xMin: 0,
+            yMin: 0,
+            xMax: 0,
+            yMax: 0,
+        };
+        let result = unsafe { FT_Outline_Get_BBox(&mut (*self.raw).outline, &mut bbox) };
+        if result == FT_Err_Ok {
+            Ok(bbox)
+        } else {
+            Err(result.into())
+        }
+    }
+
+    pub fn bitmap(&self) -> Bitmap {
+        Bitmap::new(unsafe { &(*self.raw).bitmap })
+    }
+    /// Returns `None` if the slot does not hold an outline, e.g. after
+    /// rendering or for bitmap-only fonts.
+    pub fn outline(&self) -> Option<Outline> {
+        if unsafe { (*self.raw).format } == FT_Glyph_Format__FT_GLYPH_FORMAT_OUTLINE {
+            Some(Outline::new(unsafe { &(*self.raw).outline }))
+        } else {
+            None
+        }
+    }
+
+    /// Color layers of the loaded glyph; empty if the face has no COLR v0
+    /// data for it.
+    pub fn color_layers(&self) -> ColorLayerIter {
+        unsafe { ColorLayerIter::new((*self.raw).face, (*self.raw).glyph_index) }
+    }
+}
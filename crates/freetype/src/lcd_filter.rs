@@ -0,0 +1,26 @@
+use freetype_sys::{
+    FT_LcdFilter, FT_LcdFilter__FT_LCD_FILTER_DEFAULT, FT_LcdFilter__FT_LCD_FILTER_LEGACY,
+    FT_LcdFilter__FT_LCD_FILTER_LEGACY1, FT_LcdFilter__FT_LCD_FILTER_LIGHT,
+    FT_LcdFilter__FT_LCD_FILTER_NONE,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LcdFilter {
+    None,
+    Default,
+    Light,
+    Legacy1,
+    Legacy,
+}
+
+impl From<LcdFilter> for FT_LcdFilter {
+    fn from(filter: LcdFilter) -> Self {
+        match filter {
+            LcdFilter::None => FT_LcdFilter__FT_LCD_FILTER_NONE,
+            LcdFilter::Default => FT_LcdFilter__FT_LCD_FILTER_DEFAULT,
+            LcdFilter::Light => FT_LcdFilter__FT_LCD_FILTER_LIGHT,
+            LcdFilter::Legacy1 => FT_LcdFilter__FT_LCD_FILTER_LEGACY1,
+            LcdFilter::Legacy => FT_LcdFilter__FT_LCD_FILTER_LEGACY,
+        }
+    }
+}
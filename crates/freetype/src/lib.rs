@@ -4,26 +4,62 @@ use libc::{c_long, c_void, free, malloc, realloc};
 
 pub use freetype_sys::*;
 
+mod bitmap;
+mod char_code_iter;
+mod char_map;
+mod color_layer_iter;
+mod error;
 mod face;
+mod face_flags;
+mod glyph_slot;
+mod lcd_filter;
 mod library;
+mod library_pool;
 mod memory;
 mod mm_var;
+mod outline;
+mod outline_visitor;
 mod sfnt_name;
+mod size_metrics;
+mod style_flags;
 mod var_axis;
+mod var_axis_info;
 mod var_named_style;
 
+pub use crate::bitmap::*;
+pub use crate::char_code_iter::*;
+pub use crate::char_map::*;
+pub use crate::color_layer_iter::*;
+pub use crate::error::*;
 pub use crate::face::*;
+pub use crate::face_flags::*;
+pub use crate::glyph_slot::*;
+pub use crate::lcd_filter::*;
 pub use crate::library::*;
+pub use crate::library_pool::*;
 pub use crate::memory::*;
 pub use crate::mm_var::*;
+pub use crate::outline::*;
+pub use crate::outline_visitor::*;
 pub use crate::sfnt_name::*;
+pub use crate::size_metrics::*;
+pub use crate::style_flags::*;
 pub use crate::var_axis::*;
+pub use crate::var_axis_info::*;
 pub use crate::var_named_style::*;
 
-pub fn init() -> Option<Library> {
-    let library = Library::new();
+pub fn init() -> Result<Library, Error> {
+    let library = Library::new()?;
     unsafe { FT_Add_Default_Modules(library.raw()) };
-    Some(library)
+    Ok(library)
+}
+
+pub fn fixed_to_f64(value: FT_Fixed) -> f64 {
+    value as f64 / 65536.0
+}
+
+pub fn f64_to_fixed(value: f64) -> FT_Fixed {
+    (value * 65536.0).round() as FT_Fixed
 }
 
 pub static mut MEMORY: Memory = Memory {
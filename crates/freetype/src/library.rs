@@ -1,17 +1,19 @@
 #![allow(clippy::missing_safety_doc)]
 
-use std::ptr;
+use std::{fmt, ptr, sync::Arc};
 
-use freetype_sys::{FT_Done_Library, FT_Err_Ok, FT_Library, FT_New_Library};
+use freetype_sys::{
+    FT_Done_Library, FT_Err_Ok, FT_Library, FT_Library_SetLcdFilter, FT_Library_Version,
+    FT_New_Library,
+};
 
-use crate::{Face, MEMORY};
+use crate::{Error, Face, LcdFilter, MEMORY};
 
 pub struct Library {
     raw: FT_Library,
 }
 
 unsafe impl Send for Library {}
-unsafe impl Sync for Library {}
 
 impl Library {
     pub unsafe fn raw(&self) -> FT_Library {
@@ -21,33 +23,77 @@ impl Library {
 
 impl Default for Library {
     fn default() -> Self {
-        Self::new()
+        crate::init().expect("FreeType initialization failed")
     }
 }
 
 impl Library {
-    pub fn new() -> Library {
+    pub fn new() -> Result<Library, Error> {
         let mut raw = ptr::null_mut();
         let result = unsafe { FT_New_Library(&mut MEMORY, &mut raw) };
-        assert!(result == FT_Err_Ok);
-        Library { raw }
+        if result == FT_Err_Ok {
+            Ok(Library { raw })
+        } else {
+            Err(result.into())
+        }
     }
 
     pub unsafe fn from_raw(raw: FT_Library) -> Library {
         Library { raw }
     }
 
-    pub fn face_from_file<P>(&self, path: P, face_index: isize) -> Option<Face>
+    pub fn version(&self) -> (i32, i32, i32) {
+        let (mut major, mut minor, mut patch) = (0, 0, 0);
+        unsafe { FT_Library_Version(self.raw, &mut major, &mut minor, &mut patch) };
+        (major, minor, patch)
+    }
+
+    /// Fails with `UnimplementedFeature` if FreeType was built without
+    /// subpixel rendering support.
+    pub fn set_lcd_filter(&self, filter: LcdFilter) -> Result<(), Error> {
+        let result = unsafe { FT_Library_SetLcdFilter(self.raw, filter.into()) };
+        if result == FT_Err_Ok {
+            Ok(())
+        } else {
+            Err(result.into())
+        }
+    }
+
+    pub fn face_from_file<P>(&self, path: P, face_index: isize) -> Result<Face, Error>
     where
         P: AsRef<str>,
     {
         Face::from_file(self, path, face_index)
     }
+
+    pub fn face_from_memory<'a>(
+        &'a self,
+        data: &'a [u8],
+        face_index: isize,
+    ) -> Result<Face<'a>, Error> {
+        Face::from_memory(self, data, face_index)
+    }
+
+    pub fn face_from_shared_memory(
+        &self,
+        data: Arc<Vec<u8>>,
+        face_index: isize,
+    ) -> Result<Face, Error> {
+        Face::from_shared_memory(self, data, face_index)
+    }
 }
 
 impl Drop for Library {
     fn drop(&mut self) {
-        let result = unsafe { FT_Done_Library(self.raw) };
-        assert!(result == FT_Err_Ok);
+        unsafe { FT_Done_Library(self.raw) };
+    }
+}
+
+impl fmt::Debug for Library {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Library")
+            .field("raw", &self.raw)
+            .field("version", &self.version())
+            .finish()
     }
 }
@@ -0,0 +1,77 @@
+use std::{
+    ops::Deref,
+    sync::{Mutex, PoisonError},
+};
+
+use crate::{init, Error, Library};
+
+/// Hands out one `Library` per concurrent user. `Library` is `Send` but not
+/// `Sync` or `Clone`, so a pooled library cannot be reached from two threads:
+///
+/// ```compile_fail
+/// let pool = freetype::LibraryPool::new();
+/// let library = pool.get().unwrap();
+/// std::thread::scope(|scope| {
+///     scope.spawn(|| library.version());
+/// });
+/// ```
+pub struct LibraryPool {
+    libraries: Mutex<Vec<Library>>,
+}
+
+impl Default for LibraryPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LibraryPool {
+    pub fn new() -> LibraryPool {
+        LibraryPool {
+            libraries: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Takes an idle library from the pool, or creates one if every library
+    /// is in use. The library returns to the pool when the guard is dropped.
+    pub fn get(&self) -> Result<PooledLibrary, Error> {
+        let library = self
+            .libraries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop();
+        let library = match library {
+            Some(library) => library,
+            None => init()?,
+        };
+        Ok(PooledLibrary {
+            library: Some(library),
+            pool: self,
+        })
+    }
+}
+
+pub struct PooledLibrary<'a> {
+    library: Option<Library>,
+    pool: &'a LibraryPool,
+}
+
+impl Deref for PooledLibrary<'_> {
+    type Target = Library;
+
+    fn deref(&self) -> &Library {
+        self.library.as_ref().unwrap()
+    }
+}
+
+impl Drop for PooledLibrary<'_> {
+    fn drop(&mut self) {
+        if let Some(library) = self.library.take() {
+            self.pool
+                .libraries
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(library);
+        }
+    }
+}
@@ -1,14 +1,16 @@
 #![allow(clippy::missing_safety_doc)]
 
-use std::{ptr, slice};
+use std::{fmt, ptr, slice};
 
-use freetype_sys::{FT_Done_MM_Var, FT_Err_Ok, FT_Get_MM_Var, FT_MM_Var};
+use freetype_sys::{
+    FT_Done_MM_Var, FT_Err_Ok, FT_Fixed, FT_Get_MM_Var, FT_MM_Var, FT_Set_Named_Instance,
+};
 
-use crate::{Face, Library, VarAxis, VarNamedStyle};
+use crate::{f64_to_fixed, Error, Face, VarAxis, VarAxisInfo, VarNamedStyle};
 
 pub struct MMVar<'a> {
     raw: *mut FT_MM_Var,
-    library: &'a Library,
+    face: &'a Face<'a>,
 }
 
 impl MMVar<'_> {
@@ -18,18 +20,22 @@ impl MMVar<'_> {
 }
 
 impl<'a> MMVar<'a> {
-    pub fn from_face(face: &Face, library: &'a Library) -> Option<MMVar<'a>> {
+    pub fn from_face(face: &'a Face) -> Result<MMVar<'a>, Error> {
         let mut raw = ptr::null_mut();
         let result = unsafe { FT_Get_MM_Var(face.raw(), &mut raw) };
         if result == FT_Err_Ok {
-            Some(MMVar { raw, library })
+            Ok(MMVar { raw, face })
         } else {
-            None
+            Err(result.into())
         }
     }
 
-    pub unsafe fn from_raw(raw: *mut FT_MM_Var, library: &Library) -> MMVar {
-        MMVar { raw, library }
+    pub unsafe fn from_raw(raw: *mut FT_MM_Var, face: &'a Face) -> MMVar<'a> {
+        MMVar { raw, face }
+    }
+
+    pub fn face(&self) -> &Face {
+        self.face
     }
 
     pub fn axis_count(&self) -> usize {
@@ -48,6 +54,22 @@ impl<'a> MMVar<'a> {
             .map(|(index, raw_axis)| VarAxis::new(raw_axis, self, index))
     }
 
+    pub fn axes_as_vec(&self) -> Vec<VarAxisInfo> {
+        self.axes()
+            .map(|axis| VarAxisInfo {
+                tag: axis.tag(),
+                name: self
+                    .face
+                    .sfnt_name_for(axis.name_id(), None)
+                    .or_else(|| axis.name().map(ToOwned::to_owned)),
+                min: axis.min(),
+                default: axis.default(),
+                max: axis.max(),
+                is_hidden: axis.is_hidden().unwrap_or(false),
+            })
+            .collect()
+    }
+
     pub fn named_styles(&self) -> impl Iterator<Item = VarNamedStyle> {
         let slice = unsafe {
             slice::from_raw_parts((*self.raw).namedstyle, (*self.raw).num_namedstyles as _)
@@ -56,11 +78,51 @@ impl<'a> MMVar<'a> {
             .iter()
             .map(|raw_named_style| VarNamedStyle::new(raw_named_style, self))
     }
+
+    /// Clamps user-space `user_coords` to each axis range and converts them to
+    /// the 16.16 design coordinates taken by `set_design_coords`.
+    pub fn blend_for(&self, user_coords: &[f64]) -> Result<Vec<FT_Fixed>, Error> {
+        if user_coords.len() != self.axis_count() {
+            return Err(Error::InvalidArgument);
+        }
+        Ok(self
+            .axes()
+            .zip(user_coords)
+            .map(|(axis, &value)| f64_to_fixed(value.clamp(axis.min_f64(), axis.max_f64())))
+            .collect())
+    }
+
+    pub fn set_design_coords(&self, coords: &[FT_Fixed]) -> Result<(), Error> {
+        if coords.len() != self.axis_count() {
+            return Err(Error::InvalidArgument);
+        }
+        self.face.set_var_design_coords(coords)
+    }
+
+    /// Selects the named instance at `index`, counting from 1; 0 selects the
+    /// default instance.
+    pub fn set_named_instance(&self, index: u32) -> Result<(), Error> {
+        let result = unsafe { FT_Set_Named_Instance(self.face.raw(), index) };
+        if result == FT_Err_Ok {
+            Ok(())
+        } else {
+            Err(result.into())
+        }
+    }
 }
 
 impl Drop for MMVar<'_> {
     fn drop(&mut self) {
-        let result = unsafe { FT_Done_MM_Var(self.library.raw(), self.raw) };
-        assert!(result == FT_Err_Ok);
+        unsafe { FT_Done_MM_Var(self.face.library().raw(), self.raw) };
+    }
+}
+
+impl fmt::Debug for MMVar<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MMVar")
+            .field("raw", &self.raw)
+            .field("axes", &self.axes().collect::<Vec<_>>())
+            .field("named_styles", &self.named_styles().collect::<Vec<_>>())
+            .finish()
     }
 }
@@ -0,0 +1,142 @@
+use std::slice;
+
+use freetype_sys::{
+    FT_Err_Ok, FT_Outline, FT_Outline_Contour, FT_Outline_Decompose, FT_Outline_Funcs, FT_Vector,
+};
+use libc::{c_int, c_void};
+
+use crate::{Error, OutlineVisitor};
+
+pub struct Outline<'a> {
+    raw: &'a FT_Outline,
+}
+
+impl<'a> Outline<'a> {
+    pub fn new(raw: &'a FT_Outline) -> Outline<'a> {
+        Outline { raw }
+    }
+
+    pub fn num_points(&self) -> usize {
+        self.raw.n_points as _
+    }
+
+    pub fn num_contours(&self) -> usize {
+        self.raw.n_contours as _
+    }
+
+    pub fn points(&self) -> &'a [FT_Vector] {
+        if !self.raw.points.is_null() {
+            unsafe { slice::from_raw_parts(self.raw.points, self.num_points()) }
+        } else {
+            &[]
+        }
+    }
+
+    /// One tag per point; see the `FT_CURVE_TAG_*` constants.
+    pub fn tags(&self) -> &'a [u8] {
+        if !self.raw.tags.is_null() {
+            unsafe { slice::from_raw_parts(self.raw.tags as *const u8, self.num_points()) }
+        } else {
+            &[]
+        }
+    }
+
+    /// Index of the last point of each contour.
+    pub fn contours(&self) -> &'a [FT_Outline_Contour] {
+        if !self.raw.contours.is_null() {
+            unsafe { slice::from_raw_parts(self.raw.contours, self.num_contours()) }
+        } else {
+            &[]
+        }
+    }
+
+    pub fn flags(&self) -> i32 {
+        self.raw.flags as _
+    }
+
+    pub fn decompose<V>(&self, visitor: &mut V) -> Result<(), Error>
+    where
+        V: OutlineVisitor,
+    {
+        let funcs = FT_Outline_Funcs {
+            move_to: Some(move_to::<V>),
+            line_to: Some(line_to::<V>),
+            conic_to: Some(conic_to::<V>),
+            cubic_to: Some(cubic_to::<V>),
+            shift: 0,
+            delta: 0,
+        };
+        let result = unsafe {
+            FT_Outline_Decompose(
+                self.raw as *const _ as *mut _,
+                &funcs,
+                visitor as *mut V as *mut c_void,
+            )
+        };
+        if result == FT_Err_Ok {
+            Ok(())
+        } else {
+            Err(result.into())
+        }
+    }
+}
+
+unsafe extern "C" fn move_to<V>(to: *const FT_Vector, user: *mut c_void) -> c_int
+where
+    V: OutlineVisitor,
+{
+    (*(user as *mut V)).move_to(*to);
+    0
+}
+
+unsafe extern "C" fn line_to<V>(to: *const FT_Vector, user: *mut c_void) -> c_int
+where
+    V: OutlineVisitor,
+{
+    (*(user as *mut V)).line_to(*to);
+    0
+}
+
+unsafe extern "C" fn conic_to<V>(
+    control: *const FT_Vector,
+    to: *const FT_Vector,
+    user: *mut c_void,
+) -> c_int
+where
+    V: OutlineVisitor,
+{
+    (*(user as *mut V)).conic_to(*control, *to);
+    0
+}
+
+unsafe extern "C" fn cubic_to<V>(
+    control1: *const FT_Vector,
+    control2: *const FT_Vector,
+    to: *const FT_Vector,
+    user: *mut c_void,
+) -> c_int
+where
+    V: OutlineVisitor,
+{
+    (*(user as *mut V)).cubic_to(*control1, *control2, *to);
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    const REGULAR: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../tests/fonts/FixtureSans-Regular.ttf"
+    ));
+
+    #[test]
+    fn reads_contour_end_points() {
+        let library = crate::init().unwrap();
+        let mut face = library.face_from_memory(REGULAR, 0).unwrap();
+        let load_flags = freetype_sys::FT_LOAD_NO_SCALE as i32;
+        let glyph_slot = face.load_glyph(2, load_flags).unwrap();
+        let outline = glyph_slot.outline().unwrap();
+        assert_eq!(outline.num_points(), 4);
+        assert_eq!(outline.contours(), [3]);
+    }
+}
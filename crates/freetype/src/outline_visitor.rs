@@ -0,0 +1,12 @@
+use freetype_sys::FT_Vector;
+
+/// Receives the segments of an outline from [`crate::Outline::decompose`].
+pub trait OutlineVisitor {
+    fn move_to(&mut self, to: FT_Vector);
+
+    fn line_to(&mut self, to: FT_Vector);
+
+    fn conic_to(&mut self, control: FT_Vector, to: FT_Vector);
+
+    fn cubic_to(&mut self, control1: FT_Vector, control2: FT_Vector, to: FT_Vector);
+}
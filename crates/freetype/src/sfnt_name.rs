@@ -1,8 +1,12 @@
 #![allow(clippy::missing_safety_doc)]
 
-use std::{mem, slice};
+use std::{mem, slice, str};
 
-use freetype_sys::FT_SfntName;
+use freetype_sys::{
+    FT_SfntName, TT_ISO_ID_10646, TT_ISO_ID_7BIT_ASCII, TT_ISO_ID_8859_1, TT_MAC_ID_ROMAN,
+    TT_MS_ID_SYMBOL_CS, TT_MS_ID_UCS_4, TT_MS_ID_UNICODE_CS, TT_PLATFORM_APPLE_UNICODE,
+    TT_PLATFORM_ISO, TT_PLATFORM_MACINTOSH, TT_PLATFORM_MICROSOFT,
+};
 
 pub struct SfntName {
     raw: FT_SfntName,
@@ -13,15 +17,39 @@ impl SfntName {
         SfntName { raw: mem::zeroed() }
     }
 
+    pub fn bytes(&self) -> &[u8] {
+        if !self.raw.string.is_null() {
+            unsafe { slice::from_raw_parts(self.raw.string, self.raw.string_len as _) }
+        } else {
+            &[]
+        }
+    }
+
+    #[deprecated(note = "decodes every record as UTF-16BE, use `decoded` instead")]
     pub fn name(&self) -> Option<String> {
-        let slice = unsafe { slice::from_raw_parts(self.raw.string, self.raw.string_len as _) };
-        let vec: Vec<_> = slice
+        let vec: Vec<_> = self
+            .bytes()
             .chunks_exact(2)
             .map(|item| u16::from_be_bytes([item[0], item[1]]))
             .collect();
         String::from_utf16(&vec).ok()
     }
 
+    pub fn decoded(&self) -> Option<String> {
+        let bytes = self.bytes();
+        match (self.platform_id() as u32, self.encoding_id() as u32) {
+            (TT_PLATFORM_APPLE_UNICODE, _) => decode_utf16_be(bytes),
+            (TT_PLATFORM_MACINTOSH, TT_MAC_ID_ROMAN) => Some(decode_mac_roman(bytes)),
+            (TT_PLATFORM_ISO, TT_ISO_ID_7BIT_ASCII) => decode_ascii(bytes),
+            (TT_PLATFORM_ISO, TT_ISO_ID_10646) => decode_utf16_be(bytes),
+            (TT_PLATFORM_ISO, TT_ISO_ID_8859_1) => Some(decode_latin1(bytes)),
+            (TT_PLATFORM_MICROSOFT, TT_MS_ID_SYMBOL_CS | TT_MS_ID_UNICODE_CS | TT_MS_ID_UCS_4) => {
+                decode_utf16_be(bytes)
+            }
+            _ => None,
+        }
+    }
+
     pub fn platform_id(&self) -> u16 {
         self.raw.platform_id
     }
@@ -50,3 +78,153 @@ impl AsMut<FT_SfntName> for SfntName {
         &mut self.raw
     }
 }
+
+fn decode_utf16_be(bytes: &[u8]) -> Option<String> {
+    if !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+    let vec: Vec<_> = bytes
+        .chunks_exact(2)
+        .map(|item| u16::from_be_bytes([item[0], item[1]]))
+        .collect();
+    String::from_utf16(&vec).ok()
+}
+
+fn decode_ascii(bytes: &[u8]) -> Option<String> {
+    if bytes.is_ascii() {
+        str::from_utf8(bytes).ok().map(ToOwned::to_owned)
+    } else {
+        None
+    }
+}
+
+fn decode_latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&byte| byte as char).collect()
+}
+
+fn decode_mac_roman(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&byte| {
+            if byte < 0x80 {
+                byte as char
+            } else {
+                MAC_ROMAN[byte as usize - 0x80]
+            }
+        })
+        .collect()
+}
+
+static MAC_ROMAN: [char; 128] = [
+    'Ä', 'Å', 'Ç', 'É', 'Ñ', 'Ö', 'Ü', 'á', 'à', 'â', 'ä', 'ã', 'å', 'ç', 'é', 'è', //
+    'ê', 'ë', 'í', 'ì', 'î', 'ï', 'ñ', 'ó', 'ò', 'ô', 'ö', 'õ', 'ú', 'ù', 'û', 'ü', //
+    '†', '°', '¢', '£', '§', '•', '¶', 'ß', '®', '©', '™', '´', '¨', '≠', 'Æ', 'Ø', //
+    '∞', '±', '≤', '≥', '¥', 'µ', '∂', '∑', '∏', 'π', '∫', 'ª', 'º', 'Ω', 'æ', 'ø', //
+    '¿', '¡', '¬', '√', 'ƒ', '≈', '∆', '«', '»', '…', '\u{a0}', 'À', 'Ã', 'Õ', 'Œ', 'œ', //
+    '–', '—', '“', '”', '‘', '’', '÷', '◊', 'ÿ', 'Ÿ', '⁄', '€', '‹', '›', 'ﬁ', 'ﬂ', //
+    '‡', '·', '‚', '„', '‰', 'Â', 'Ê', 'Á', 'Ë', 'È', 'Í', 'Î', 'Ï', 'Ì', 'Ó', 'Ô', //
+    '\u{f8ff}', 'Ò', 'Ú', 'Û', 'Ù', 'ı', 'ˆ', '˜', '¯', '˘', '˙', '˚', '¸', '˝', '˛', 'ˇ', //
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decoded(platform_id: u32, encoding_id: u32, bytes: &[u8]) -> Option<String> {
+        let mut sfnt_name = unsafe { SfntName::new() };
+        let raw = sfnt_name.as_mut();
+        raw.platform_id = platform_id as _;
+        raw.encoding_id = encoding_id as _;
+        raw.string = bytes.as_ptr() as _;
+        raw.string_len = bytes.len() as _;
+        sfnt_name.decoded()
+    }
+
+    fn utf16_be(value: &str) -> Vec<u8> {
+        value.encode_utf16().flat_map(u16::to_be_bytes).collect()
+    }
+
+    #[test]
+    fn decodes_windows_unicode_as_utf16_be() {
+        let bytes = utf16_be("Noto Sans 日本語");
+        let expected = Some("Noto Sans 日本語".to_owned());
+        assert_eq!(
+            decoded(TT_PLATFORM_MICROSOFT, TT_MS_ID_UNICODE_CS, &bytes),
+            expected
+        );
+        assert_eq!(
+            decoded(TT_PLATFORM_MICROSOFT, TT_MS_ID_SYMBOL_CS, &bytes),
+            expected
+        );
+        assert_eq!(
+            decoded(TT_PLATFORM_MICROSOFT, TT_MS_ID_UCS_4, &bytes),
+            expected
+        );
+    }
+
+    #[test]
+    fn decodes_apple_unicode_and_iso_10646_as_utf16_be() {
+        let bytes = utf16_be("Inter");
+        assert_eq!(
+            decoded(TT_PLATFORM_APPLE_UNICODE, 3, &bytes),
+            Some("Inter".to_owned())
+        );
+        assert_eq!(
+            decoded(TT_PLATFORM_ISO, TT_ISO_ID_10646, &bytes),
+            Some("Inter".to_owned())
+        );
+    }
+
+    #[test]
+    fn decodes_mac_roman_high_bytes() {
+        let bytes = b"Caf\x8E \xA5 \xDB\xF0";
+        assert_eq!(
+            decoded(TT_PLATFORM_MACINTOSH, TT_MAC_ID_ROMAN, bytes),
+            Some("Café • €\u{f8ff}".to_owned())
+        );
+    }
+
+    #[test]
+    fn decodes_latin1() {
+        let bytes = b"Caf\xE9 \xD8";
+        assert_eq!(
+            decoded(TT_PLATFORM_ISO, TT_ISO_ID_8859_1, bytes),
+            Some("Café Ø".to_owned())
+        );
+    }
+
+    #[test]
+    fn decodes_ascii_and_rejects_high_bytes() {
+        assert_eq!(
+            decoded(TT_PLATFORM_ISO, TT_ISO_ID_7BIT_ASCII, b"Inter"),
+            Some("Inter".to_owned())
+        );
+        assert_eq!(
+            decoded(TT_PLATFORM_ISO, TT_ISO_ID_7BIT_ASCII, b"Caf\xE9"),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_odd_length_utf16() {
+        let mut bytes = utf16_be("Inter");
+        bytes.push(0);
+        assert_eq!(
+            decoded(TT_PLATFORM_MICROSOFT, TT_MS_ID_UNICODE_CS, &bytes),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_platforms_and_encodings() {
+        assert_eq!(decoded(7, 0, b"Inter"), None);
+        assert_eq!(decoded(TT_PLATFORM_MACINTOSH, 1, b"Inter"), None);
+    }
+
+    #[test]
+    fn decodes_empty_records() {
+        let sfnt_name = unsafe { SfntName::new() };
+        assert_eq!(sfnt_name.bytes(), &[] as &[u8]);
+        assert_eq!(sfnt_name.decoded(), Some(String::new()));
+    }
+}
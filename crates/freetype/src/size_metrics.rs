@@ -0,0 +1,52 @@
+use freetype_sys::FT_Size_Metrics;
+
+/// Metrics of the current size. Lengths are in 26.6 fixed-point pixels and
+/// scales are in 16.16 fixed-point.
+#[derive(Clone, Copy)]
+pub struct SizeMetrics {
+    raw: FT_Size_Metrics,
+}
+
+impl SizeMetrics {
+    pub fn new(raw: FT_Size_Metrics) -> SizeMetrics {
+        SizeMetrics { raw }
+    }
+
+    pub fn x_ppem(&self) -> u16 {
+        self.raw.x_ppem
+    }
+
+    pub fn y_ppem(&self) -> u16 {
+        self.raw.y_ppem
+    }
+
+    pub fn x_scale(&self) -> i32 {
+        self.raw.x_scale as _
+    }
+
+    pub fn y_scale(&self) -> i32 {
+        self.raw.y_scale as _
+    }
+
+    pub fn ascender(&self) -> i32 {
+        self.raw.ascender as _
+    }
+
+    pub fn descender(&self) -> i32 {
+        self.raw.descender as _
+    }
+
+    pub fn height(&self) -> i32 {
+        self.raw.height as _
+    }
+
+    pub fn max_advance(&self) -> i32 {
+        self.raw.max_advance as _
+    }
+}
+
+impl AsRef<FT_Size_Metrics> for SizeMetrics {
+    fn as_ref(&self) -> &FT_Size_Metrics {
+        &self.raw
+    }
+}
@@ -0,0 +1,10 @@
+use bitflags::bitflags;
+use freetype_sys::{FT_STYLE_FLAG_BOLD, FT_STYLE_FLAG_ITALIC};
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct StyleFlags: u32 {
+        const ITALIC = FT_STYLE_FLAG_ITALIC;
+        const BOLD = FT_STYLE_FLAG_BOLD;
+    }
+}
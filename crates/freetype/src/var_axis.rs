@@ -1,6 +1,8 @@
+use std::{ffi::CStr, fmt};
+
 use freetype_sys::{FT_Err_Ok, FT_Get_Var_Axis_Flags, FT_Var_Axis, FT_VAR_AXIS_FLAG_HIDDEN};
 
-use crate::MMVar;
+use crate::{fixed_to_f64, Error, MMVar};
 
 pub struct VarAxis<'a> {
     raw: &'a FT_Var_Axis,
@@ -13,43 +15,93 @@ impl<'a> VarAxis<'a> {
         VarAxis { raw, mm_var, index }
     }
 
-    pub fn tag(&self) -> u32 {
-        self.raw.tag as _
+    pub fn tag(&self) -> [u8; 4] {
+        (self.raw.tag as u32).to_be_bytes()
     }
 
     pub fn tag_string(&self) -> Option<String> {
-        String::from_utf8(self.tag().to_be_bytes().into()).ok()
+        String::from_utf8(self.tag().into()).ok()
+    }
+
+    /// Axis name as stored by FreeType, usually the English name. Returns
+    /// `None` if it is missing or not valid UTF-8.
+    pub fn name(&self) -> Option<&str> {
+        if !self.raw.name.is_null() {
+            unsafe { CStr::from_ptr(self.raw.name) }.to_str().ok()
+        } else {
+            None
+        }
     }
 
     pub fn name_id(&self) -> u16 {
         self.raw.strid as _
     }
 
+    /// Minimum axis value in 16.16 fixed-point.
     pub fn min(&self) -> i32 {
         self.raw.minimum as _
     }
 
+    /// Maximum axis value in 16.16 fixed-point.
     pub fn max(&self) -> i32 {
         self.raw.maximum as _
     }
 
+    /// Default axis value in 16.16 fixed-point.
     pub fn default(&self) -> i32 {
         self.raw.def as _
     }
 
-    pub fn flags(&self) -> Option<u16> {
+    pub fn min_f64(&self) -> f64 {
+        fixed_to_f64(self.raw.minimum)
+    }
+
+    pub fn max_f64(&self) -> f64 {
+        fixed_to_f64(self.raw.maximum)
+    }
+
+    pub fn default_f64(&self) -> f64 {
+        fixed_to_f64(self.raw.def)
+    }
+
+    /// Maps a user-space value onto the normalized [-1, 1] range, with the
+    /// default value at 0. Out-of-range values are clamped.
+    pub fn normalize(&self, user_value: f64) -> f64 {
+        let (min, default, max) = (self.min_f64(), self.default_f64(), self.max_f64());
+        let value = user_value.clamp(min, max);
+        if value < default {
+            (value - default) / (default - min)
+        } else if value > default {
+            (value - default) / (max - default)
+        } else {
+            0.0
+        }
+    }
+
+    pub fn flags(&self) -> Result<u16, Error> {
         let mut flags = Default::default();
         let result =
             unsafe { FT_Get_Var_Axis_Flags(self.mm_var.raw(), self.index as _, &mut flags) };
         if result == FT_Err_Ok {
-            Some(flags as _)
+            Ok(flags as _)
         } else {
-            None
+            Err(result.into())
         }
     }
 
-    pub fn is_hidden(&self) -> Option<bool> {
+    pub fn is_hidden(&self) -> Result<bool, Error> {
         let flags = self.flags()?;
-        Some(flags & (FT_VAR_AXIS_FLAG_HIDDEN as u16) != 0)
+        Ok(flags & (FT_VAR_AXIS_FLAG_HIDDEN as u16) != 0)
+    }
+}
+
+impl fmt::Debug for VarAxis<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VarAxis")
+            .field("tag", &self.tag_string())
+            .field("min", &self.min_f64())
+            .field("default", &self.default_f64())
+            .field("max", &self.max_f64())
+            .finish()
     }
 }
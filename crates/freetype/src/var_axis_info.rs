@@ -0,0 +1,13 @@
+/// Owned copy of a [`crate::VarAxis`] that can outlive its face.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VarAxisInfo {
+    pub tag: [u8; 4],
+    pub name: Option<String>,
+    /// Minimum axis value in 16.16 fixed-point.
+    pub min: i32,
+    /// Default axis value in 16.16 fixed-point.
+    pub default: i32,
+    /// Maximum axis value in 16.16 fixed-point.
+    pub max: i32,
+    pub is_hidden: bool,
+}
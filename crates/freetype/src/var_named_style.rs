@@ -1,8 +1,8 @@
-use std::slice;
+use std::{fmt, slice};
 
 use freetype_sys::FT_Var_Named_Style;
 
-use crate::MMVar;
+use crate::{fixed_to_f64, MMVar};
 
 pub struct VarNamedStyle<'a> {
     raw: &'a FT_Var_Named_Style,
@@ -26,8 +26,34 @@ impl<'a> VarNamedStyle<'a> {
         }
     }
 
+    pub fn name(&self) -> Option<String> {
+        self.mm_var.face().sfnt_name_for(self.name_id(), None)
+    }
+
+    pub fn postscript_name(&self) -> Option<String> {
+        self.mm_var
+            .face()
+            .sfnt_name_for(self.postscript_name_id()?, None)
+    }
+
+    /// Design coordinates in 16.16 fixed-point, one per axis.
     pub fn coordinates(&self) -> impl Iterator<Item = i32> {
         let slice = unsafe { slice::from_raw_parts(self.raw.coords, self.mm_var.axis_count()) };
         slice.iter().map(|&coordinate| coordinate as _)
     }
+
+    pub fn coordinates_f64(&self) -> impl Iterator<Item = f64> {
+        let slice = unsafe { slice::from_raw_parts(self.raw.coords, self.mm_var.axis_count()) };
+        slice.iter().map(|&coordinate| fixed_to_f64(coordinate))
+    }
+}
+
+impl fmt::Debug for VarNamedStyle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VarNamedStyle")
+            .field("name_id", &self.name_id())
+            .field("postscript_name_id", &self.postscript_name_id())
+            .field("coordinates", &self.coordinates_f64().collect::<Vec<_>>())
+            .finish()
+    }
 }
@@ -0,0 +1,215 @@
+#!/usr/bin/env python3
+"""Writes the minimal TrueType fonts used by the tests to tests/fonts."""
+
+import struct
+from pathlib import Path
+
+OUT_DIR = Path(__file__).resolve().parent.parent / "tests" / "fonts"
+
+UNITS_PER_EM = 1000
+CMAP = {0x20: 1, 0x41: 2}
+
+
+def checksum(data):
+    data += b"\0" * (-len(data) % 4)
+    return sum(struct.unpack(f">{len(data) // 4}I", data)) & 0xFFFFFFFF
+
+
+def head(mac_style):
+    return struct.pack(
+        ">IIIIHHqqhhhhHHhhh",
+        0x00010000, 0x00010000, 0, 0x5F0F3CF5, 0x000B, UNITS_PER_EM,
+        0, 0, 0, 0, 600, 700, mac_style, 8, 2, 0, 0,
+    )
+
+
+def hhea(num_glyphs):
+    return struct.pack(
+        ">IhhhHhhhhhh8xhH",
+        0x00010000, 800, -200, 0, 600, 0, 0, 600, 1, 0, 0, 0, num_glyphs,
+    )
+
+
+def maxp(num_glyphs):
+    return struct.pack(">IH13H", 0x00010000, num_glyphs, 4, 1, 0, 0, 2, *[0] * 8)
+
+
+def os2(weight_class, fs_selection):
+    return struct.pack(
+        ">HhHHH8hhhh10s4I4sHHHhhhHHIIhhHHH",
+        4, 450, weight_class, 5, 0,
+        650, 600, 0, 75, 650, 600, 0, 350, 50, 250,
+        0, bytes(10), 1, 0, 0, 0, b"NONE", fs_selection, 0x20, 0x41,
+        800, -200, 0, 800, 200, 1, 0, 500, 700, 0, 0x20, 0,
+    )
+
+
+def post():
+    return struct.pack(">IihhI4I", 0x00030000, 0, -100, 50, 0, 0, 0, 0, 0)
+
+
+def cmap():
+    segments = sorted(CMAP.items()) + [(0xFFFF, 0)]
+    seg_count = len(segments)
+    search_range = 2 * 2 ** (seg_count.bit_length() - 1)
+    subtable = struct.pack(
+        ">HHHHHHH", 4, 16 + 8 * seg_count, 0, 2 * seg_count,
+        search_range, seg_count.bit_length() - 1, 2 * seg_count - search_range,
+    )
+    subtable += struct.pack(f">{seg_count}H", *(code for code, _ in segments))
+    subtable += struct.pack(">H", 0)
+    subtable += struct.pack(f">{seg_count}H", *(code for code, _ in segments))
+    subtable += struct.pack(
+        f">{seg_count}H", *((glyph - code) % 0x10000 for code, glyph in segments)
+    )
+    subtable += struct.pack(f">{seg_count}H", *[0] * seg_count)
+    return struct.pack(">HHHHIHHI", 0, 2, 0, 3, 20, 3, 1, 20) + subtable
+
+
+def glyf_loca_hmtx():
+    rectangle = struct.pack(">hhhhhH", 1, 100, 0, 500, 700, 3)
+    rectangle += struct.pack(">H4B", 0, 1, 1, 1, 1)
+    rectangle += struct.pack(">4h", 100, 0, 400, 0)
+    rectangle += struct.pack(">4h", 0, 700, 0, -700)
+    glyphs = [b"", b"", rectangle]
+    offsets = [0]
+    for glyph in glyphs:
+        offsets.append(offsets[-1] + len(glyph))
+    loca = struct.pack(f">{len(offsets)}H", *(offset // 2 for offset in offsets))
+    hmtx = struct.pack(">6h", 500, 0, 250, 0, 600, 100)
+    return b"".join(glyphs), loca, hmtx, len(glyphs)
+
+
+def name(records):
+    mac = [(1, 0, 0, name_id, value.encode("mac_roman")) for name_id, value in records if name_id < 256]
+    win = [(3, 1, 0x409, name_id, value.encode("utf-16-be")) for name_id, value in records]
+    entries = sorted(mac + win)
+    header = struct.pack(">HHH", 0, len(entries), 6 + 12 * len(entries))
+    strings = b""
+    for platform_id, encoding_id, language_id, name_id, value in entries:
+        header += struct.pack(
+            ">6H", platform_id, encoding_id, language_id, name_id, len(value), len(strings)
+        )
+        strings += value
+    return header + strings
+
+
+def fvar(axes, instances):
+    data = struct.pack(">HHHHHHHH", 1, 0, 16, 2, len(axes), 20, len(instances), 4 * len(axes) + 6)
+    for tag, minimum, default, maximum, name_id in axes:
+        data += struct.pack(">4siiiHH", tag, minimum << 16, default << 16, maximum << 16, 0, name_id)
+    for name_id, coordinates, postscript_name_id in instances:
+        data += struct.pack(">HH", name_id, 0)
+        data += struct.pack(f">{len(coordinates)}i", *(value << 16 for value in coordinates))
+        data += struct.pack(">H", postscript_name_id)
+    return data
+
+
+def gvar(axis_count, glyph_count):
+    # No glyph has variation data; FreeType still wants the table to exist.
+    offsets_size = 2 * (glyph_count + 1)
+    data = struct.pack(
+        ">HHHHIHHI", 1, 0, axis_count, 0, 20 + offsets_size, glyph_count, 0, 20 + offsets_size
+    )
+    return data + bytes(offsets_size)
+
+
+def tables(family, style, weight_class, extra_names=(), extra_tables=None):
+    bold = weight_class >= 700
+    postscript_name = f"{family}-{style}".replace(" ", "")
+    glyf, loca, hmtx, num_glyphs = glyf_loca_hmtx()
+    result = {
+        b"OS/2": os2(weight_class, 0x20 if bold else 0x40),
+        b"cmap": cmap(),
+        b"glyf": glyf,
+        b"head": head(1 if bold else 0),
+        b"hhea": hhea(num_glyphs),
+        b"hmtx": hmtx,
+        b"loca": loca,
+        b"maxp": maxp(num_glyphs),
+        b"name": name([
+            (1, family),
+            (2, style),
+            (3, f"{postscript_name};fixture"),
+            (4, f"{family} {style}"),
+            (6, postscript_name),
+            *extra_names,
+        ]),
+        b"post": post(),
+    }
+    result.update(extra_tables or {})
+    return dict(sorted(result.items()))
+
+
+def directory_size(tables):
+    return 12 + 16 * len(tables)
+
+
+def table_directory(tables, offset):
+    num_tables = len(tables)
+    search_range = 16 * 2 ** (num_tables.bit_length() - 1)
+    directory = struct.pack(
+        ">IHHHH", 0x00010000, num_tables, search_range,
+        num_tables.bit_length() - 1, 16 * num_tables - search_range,
+    )
+    data = b""
+    for tag, table in tables.items():
+        directory += struct.pack(">4sIII", tag, checksum(table), offset + len(data), len(table))
+        data += table + b"\0" * (-len(table) % 4)
+    return directory, data
+
+
+def font(tables):
+    directory, data = table_directory(tables, directory_size(tables))
+    font = directory + data
+    # Patch head.checkSumAdjustment now that the whole file is known.
+    head_offset = struct.unpack(">I", font[directory.index(b"head") + 8:][:4])[0]
+    adjustment = (0xB1B0AFBA - checksum(font)) & 0xFFFFFFFF
+    return font[:head_offset + 8] + struct.pack(">I", adjustment) + font[head_offset + 12:]
+
+
+def collection(fonts):
+    header_size = 12 + 4 * len(fonts)
+    offsets, body = [], b""
+    for tables in fonts:
+        offsets.append(header_size + len(body))
+        directory, data = table_directory(tables, offsets[-1] + directory_size(tables))
+        body += directory + data
+    header = struct.pack(f">4sIII{len(fonts) - 1}I", b"ttcf", 0x00010000, len(fonts), *offsets)
+    return header + body
+
+
+def main():
+    OUT_DIR.mkdir(parents=True, exist_ok=True)
+    (OUT_DIR / "FixtureSans-Regular.ttf").write_bytes(
+        font(tables("Fixture Sans", "Regular", 400))
+    )
+    (OUT_DIR / "FixtureSansVariable.ttf").write_bytes(
+        font(tables(
+            "Fixture Sans Variable", "Regular", 400,
+            extra_names=[
+                (256, "Weight"),
+                (257, "Light"),
+                (258, "FixtureSansVariable-Light"),
+                (259, "Bold"),
+                (260, "FixtureSansVariable-Bold"),
+            ],
+            extra_tables={
+                b"fvar": fvar(
+                    axes=[(b"wght", 100, 400, 900, 256)],
+                    instances=[(257, [300], 258), (259, [700], 260)],
+                ),
+                b"gvar": gvar(axis_count=1, glyph_count=3),
+            },
+        ))
+    )
+    (OUT_DIR / "FixtureSans.ttc").write_bytes(
+        collection([
+            tables("Fixture Sans", "Regular", 400),
+            tables("Fixture Sans", "Bold", 700),
+        ])
+    )
+
+
+if __name__ == "__main__":
+    main()